@@ -0,0 +1,324 @@
+//! Derive macros for `cougr-core`
+//!
+//! `#[derive(Component)]`, `#[derive(Resource)]` and `#[derive(Event)]`
+//! implement the matching `cougr_core` trait together with
+//! `cougr_core::codec::Codec`, which provides the byte encoding. Use
+//! `#[derive(Codec)]` on plain types that are only nested inside other
+//! components. Each type should use exactly one of these derives.
+//!
+//! The type symbol defaults to the lowercased type name and can be set with
+//! `#[component(name = "...")]`, `#[resource(name = "...")]` or
+//! `#[event(name = "...")]`. Components also accept
+//! `#[component(storage = "table" | "sparse")]`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Generics, Ident, LitStr,
+    Result,
+};
+
+/// Derive `cougr_core::component::ComponentTrait` and `Codec`
+#[proc_macro_derive(Component, attributes(component))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, Kind::Component)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `cougr_core::resource::ResourceTrait` and `Codec`
+#[proc_macro_derive(Resource, attributes(resource))]
+pub fn derive_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, Kind::Resource)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `cougr_core::event::EventTrait` and `Codec`
+#[proc_macro_derive(Event, attributes(event))]
+pub fn derive_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, Kind::Event)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive only `cougr_core::codec::Codec`
+#[proc_macro_derive(Codec)]
+pub fn derive_codec(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, Kind::Codec)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Component,
+    Resource,
+    Event,
+    Codec,
+}
+
+impl Kind {
+    fn attribute(self) -> &'static str {
+        match self {
+            Kind::Component => "component",
+            Kind::Resource => "resource",
+            Kind::Event => "event",
+            Kind::Codec => "codec",
+        }
+    }
+}
+
+/// Options parsed from the `#[component(...)]`-style attribute
+struct Options {
+    name: String,
+    name_span: Span,
+    sparse: bool,
+}
+
+fn parse_options(input: &DeriveInput, kind: Kind) -> Result<Options> {
+    let mut options = Options {
+        name: input.ident.to_string().to_lowercase(),
+        name_span: input.ident.span(),
+        sparse: false,
+    };
+    for attr in &input.attrs {
+        if !attr.path().is_ident(kind.attribute()) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let lit: LitStr = meta.value()?.parse()?;
+                options.name = lit.value();
+                options.name_span = lit.span();
+                Ok(())
+            } else if meta.path.is_ident("storage") && kind == Kind::Component {
+                let lit: LitStr = meta.value()?.parse()?;
+                options.sparse = match lit.value().as_str() {
+                    "table" => false,
+                    "sparse" => true,
+                    _ => return Err(Error::new(lit.span(), "expected \"table\" or \"sparse\"")),
+                };
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute"))
+            }
+        })?;
+    }
+
+    let valid_chars = options
+        .name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if options.name.is_empty() || options.name.len() > 9 || !valid_chars {
+        return Err(Error::new(
+            options.name_span,
+            format!(
+                "`{}` is not a valid short symbol (1-9 characters of [a-zA-Z0-9_]); \
+                 set one with #[{}(name = \"...\")]",
+                options.name,
+                kind.attribute()
+            ),
+        ));
+    }
+    Ok(options)
+}
+
+fn expand(input: &DeriveInput, kind: Kind) -> Result<TokenStream2> {
+    let codec = expand_codec(input)?;
+    if kind == Kind::Codec {
+        return Ok(codec);
+    }
+
+    let options = parse_options(input, kind)?;
+    let ident = &input.ident;
+    let generics = codec_generics(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &options.name;
+    let body = quote! {
+        fn serialize(&self, env: &::soroban_sdk::Env) -> ::soroban_sdk::Bytes {
+            ::cougr_core::codec::to_bytes(env, self)
+        }
+
+        fn deserialize(
+            _env: &::soroban_sdk::Env,
+            data: &::soroban_sdk::Bytes,
+        ) -> ::core::option::Option<Self> {
+            ::cougr_core::codec::from_bytes(data)
+        }
+    };
+
+    let trait_impl = match kind {
+        Kind::Component => {
+            let storage = if options.sparse {
+                quote!(::cougr_core::component::ComponentStorage::Sparse)
+            } else {
+                quote!(::cougr_core::component::ComponentStorage::Table)
+            };
+            quote! {
+                impl #impl_generics ::cougr_core::component::ComponentTrait
+                    for #ident #ty_generics #where_clause
+                {
+                    fn component_type() -> ::soroban_sdk::Symbol {
+                        ::soroban_sdk::symbol_short!(#name)
+                    }
+
+                    #body
+
+                    fn default_storage() -> ::cougr_core::component::ComponentStorage {
+                        #storage
+                    }
+                }
+            }
+        }
+        Kind::Resource => quote! {
+            impl #impl_generics ::cougr_core::resource::ResourceTrait
+                for #ident #ty_generics #where_clause
+            {
+                fn resource_type() -> ::soroban_sdk::Symbol {
+                    ::soroban_sdk::symbol_short!(#name)
+                }
+
+                #body
+            }
+        },
+        Kind::Event => quote! {
+            impl #impl_generics ::cougr_core::event::EventTrait
+                for #ident #ty_generics #where_clause
+            {
+                fn event_type() -> ::soroban_sdk::Symbol {
+                    ::soroban_sdk::symbol_short!(#name)
+                }
+
+                #body
+            }
+        },
+        Kind::Codec => unreachable!(),
+    };
+
+    Ok(quote! {
+        #codec
+        #trait_impl
+    })
+}
+
+/// Add a `Codec` bound to every type parameter
+fn codec_generics(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::cougr_core::codec::Codec));
+    }
+    generics
+}
+
+/// Bindings for the fields of a struct or variant, in declaration order
+///
+/// Named fields are not bound by their own names, which could shadow the
+/// `out` and `input` parameters of the generated code.
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.len())
+        .map(|i| format_ident!("__field_{}", i))
+        .collect()
+}
+
+/// Pattern (or constructor) matching `fields` with the given bindings
+fn fields_pattern(path: TokenStream2, fields: &Fields, bindings: &[Ident]) -> TokenStream2 {
+    match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+fn expand_codec(input: &DeriveInput) -> Result<TokenStream2> {
+    let ident = &input.ident;
+    let generics = codec_generics(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let (encode, decode) = match &input.data {
+        Data::Struct(data) => {
+            let bindings = field_bindings(&data.fields);
+            let pattern = fields_pattern(quote!(Self), &data.fields, &bindings);
+            (
+                quote! {
+                    let #pattern = self;
+                    #(::cougr_core::codec::Codec::encode(#bindings, out);)*
+                },
+                quote! {
+                    #(let #bindings = ::cougr_core::codec::Codec::decode(input)?;)*
+                    ::core::option::Option::Some(#pattern)
+                },
+            )
+        }
+        Data::Enum(data) => {
+            if data.variants.len() > 256 {
+                return Err(Error::new_spanned(
+                    ident,
+                    "enums with more than 256 variants are not supported",
+                ));
+            }
+            let mut encode_arms = Vec::new();
+            let mut decode_arms = Vec::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                let tag = index as u8;
+                let variant_ident = &variant.ident;
+                let bindings = field_bindings(&variant.fields);
+                let pattern =
+                    fields_pattern(quote!(Self::#variant_ident), &variant.fields, &bindings);
+                encode_arms.push(quote! {
+                    #pattern => {
+                        out.push_back(#tag);
+                        #(::cougr_core::codec::Codec::encode(#bindings, out);)*
+                    }
+                });
+                decode_arms.push(quote! {
+                    #tag => {
+                        #(let #bindings = ::cougr_core::codec::Codec::decode(input)?;)*
+                        ::core::option::Option::Some(#pattern)
+                    }
+                });
+            }
+            (
+                quote! {
+                    match self {
+                        #(#encode_arms)*
+                    }
+                },
+                quote! {
+                    let tag: u8 = ::cougr_core::codec::Codec::decode(input)?;
+                    match tag {
+                        #(#decode_arms)*
+                        _ => ::core::option::Option::None,
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(ident, "unions are not supported"));
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::cougr_core::codec::Codec for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn encode(&self, out: &mut ::soroban_sdk::Bytes) {
+                #encode
+            }
+
+            #[allow(unused_variables)]
+            fn decode(
+                input: &mut ::cougr_core::codec::Decoder,
+            ) -> ::core::option::Option<Self> {
+                #decode
+            }
+        }
+    })
+}
@@ -0,0 +1,322 @@
+//! Change detection
+//!
+//! The world stamps every component and resource with the change tick at
+//! which it was added and at which it last changed. A system remembers the
+//! tick it last ran at, so it can pick out what changed since:
+//!
+//! - [`Added<T>`] and [`Changed<T>`] filter a [`QueryParam`] or
+//!   [`World::query_filtered`] to entities whose `T` was added, or added or
+//!   changed, since the last run.
+//! - [`Res::is_changed`] and [`Res::is_added`] do the same for resources.
+//!
+//! The change tick starts at 1 and is advanced after every run of a function
+//! system with parameters, and at the end of every schedule run. A system's
+//! own changes are stamped with the tick it ran at, so it does not see them
+//! again on its next run; changes by anyone else are seen exactly once.
+//! Named systems (see [`System::set_name`]) save the tick they last ran at
+//! with the world, so this holds across contract invocations too.
+//!
+//! Writes count as changes when they go through the world: inserting a
+//! component or resource, [`World::get_component_mut`], [`World::modify`],
+//! and typed write-backs that change the encoded value.
+//!
+//! [`QueryParam`]: crate::system::QueryParam
+//! [`System::set_name`]: crate::system::System::set_name
+//! [`Res::is_changed`]: crate::system::Res::is_changed
+//! [`Res::is_added`]: crate::system::Res::is_added
+
+use crate::component::ComponentTrait;
+use crate::entity::EntityId;
+use crate::query::{Query, QueryData};
+use crate::world::World;
+use alloc::vec::Vec as StdVec;
+use core::marker::PhantomData;
+use soroban_sdk::{contracttype, Env, Map, Symbol};
+
+/// Change ticks at which a component or resource was added and last changed
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComponentTicks {
+    pub added: u64,
+    pub changed: u64,
+}
+
+impl ComponentTicks {
+    /// Ticks of a value added at `tick`
+    pub fn new(tick: u64) -> Self {
+        Self {
+            added: tick,
+            changed: tick,
+        }
+    }
+
+    /// Check if the value was added after `last_run`
+    pub fn is_added(&self, last_run: u64) -> bool {
+        self.added > last_run
+    }
+
+    /// Check if the value was added or changed after `last_run`
+    pub fn is_changed(&self, last_run: u64) -> bool {
+        self.changed > last_run
+    }
+}
+
+/// Change detection state of a world, saved with it
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ChangeTicks {
+    /// Current change tick
+    pub tick: u64,
+    /// Ticks of each component, keyed by packed entity bits and type
+    pub components: Map<(u64, Symbol), ComponentTicks>,
+    /// Ticks of each resource, keyed by type
+    pub resources: Map<Symbol, ComponentTicks>,
+    /// Tick at which each component type was last added to or removed from
+    /// an entity
+    pub structure: Map<Symbol, u64>,
+    /// Tick at which each named system last ran
+    pub systems: Map<Symbol, u64>,
+}
+
+impl ChangeTicks {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            tick: 1,
+            components: Map::new(env),
+            resources: Map::new(env),
+            structure: Map::new(env),
+            systems: Map::new(env),
+        }
+    }
+
+    /// Stamp a component as added, or as changed if it was already present
+    pub fn mark_component(&mut self, entity_id: EntityId, component_type: &Symbol) {
+        let key = (entity_id.to_bits(), component_type.clone());
+        let ticks = match self.components.get(key.clone()) {
+            Some(ticks) => ComponentTicks {
+                changed: self.tick,
+                ..ticks
+            },
+            None => {
+                self.structure.set(component_type.clone(), self.tick);
+                ComponentTicks::new(self.tick)
+            }
+        };
+        self.components.set(key, ticks);
+    }
+
+    /// Forget the ticks of a removed component
+    pub fn remove_component(&mut self, entity_id: EntityId, component_type: &Symbol) {
+        let key = (entity_id.to_bits(), component_type.clone());
+        if self.components.contains_key(key.clone()) {
+            self.components.remove(key);
+            self.structure.set(component_type.clone(), self.tick);
+        }
+    }
+
+    /// Stamp a resource as added, or as changed if it was already present
+    pub fn mark_resource(&mut self, resource_type: &Symbol) {
+        let ticks = match self.resources.get(resource_type.clone()) {
+            Some(ticks) => ComponentTicks {
+                changed: self.tick,
+                ..ticks
+            },
+            None => ComponentTicks::new(self.tick),
+        };
+        self.resources.set(resource_type.clone(), ticks);
+    }
+}
+
+impl World {
+    /// Get the current change tick
+    pub fn change_tick(&self) -> u64 {
+        self.change_ticks.tick
+    }
+
+    /// Advance the change tick, returning the new value
+    ///
+    /// Called after each function system run and by
+    /// [`Schedule::run`](crate::scheduler::Schedule::run).
+    pub fn increment_change_tick(&mut self) -> u64 {
+        self.change_ticks.tick += 1;
+        self.change_ticks.tick
+    }
+
+    /// Get the change ticks of a component on an entity
+    pub fn component_ticks(
+        &self,
+        entity_id: EntityId,
+        component_type: &Symbol,
+    ) -> Option<ComponentTicks> {
+        self.change_ticks
+            .components
+            .get((entity_id.to_bits(), component_type.clone()))
+    }
+
+    /// Get the change ticks of a resource
+    pub fn resource_ticks(&self, resource_type: &Symbol) -> Option<ComponentTicks> {
+        self.change_ticks.resources.get(resource_type.clone())
+    }
+
+    /// Get the tick at which `component_type` was last added to or removed
+    /// from any entity, or 0 if never
+    pub fn structure_tick(&self, component_type: &Symbol) -> u64 {
+        self.change_ticks
+            .structure
+            .get(component_type.clone())
+            .unwrap_or(0)
+    }
+
+    /// Get the tick at which the system named `name` last ran, or 0 if never
+    pub fn system_last_run(&self, name: &Symbol) -> u64 {
+        self.change_ticks.systems.get(name.clone()).unwrap_or(0)
+    }
+
+    /// Record the tick at which the system named `name` ran
+    pub fn set_system_last_run(&mut self, name: &Symbol, tick: u64) {
+        self.change_ticks.systems.set(name.clone(), tick);
+    }
+
+    /// Decode the components in `Q` for every entity that has all of them and
+    /// passes filter `F` since `last_run`
+    ///
+    /// Use [`Query::fetch_filtered`] to add `with`/`without` filters.
+    pub fn query_filtered<Q: QueryData, F: ChangeFilter>(&self, last_run: u64) -> StdVec<Q::Item> {
+        Query::new(self.env()).fetch_filtered::<Q, F>(self, last_run)
+    }
+}
+
+/// Filter on the change ticks of an entity's components
+///
+/// Implemented by [`Added`], [`Changed`], `()` (matching everything) and
+/// tuples of up to four filters, which match when all of them do.
+pub trait ChangeFilter {
+    /// Check if `entity_id` passes the filter since `last_run`
+    fn matches(world: &World, entity_id: EntityId, last_run: u64) -> bool;
+}
+
+/// Filter for entities whose `T` was added since the last run
+pub struct Added<T>(PhantomData<T>);
+
+/// Filter for entities whose `T` was added or changed since the last run
+pub struct Changed<T>(PhantomData<T>);
+
+impl<T: ComponentTrait> ChangeFilter for Added<T> {
+    fn matches(world: &World, entity_id: EntityId, last_run: u64) -> bool {
+        world
+            .component_ticks(entity_id, &T::component_type())
+            .is_some_and(|ticks| ticks.is_added(last_run))
+    }
+}
+
+impl<T: ComponentTrait> ChangeFilter for Changed<T> {
+    fn matches(world: &World, entity_id: EntityId, last_run: u64) -> bool {
+        world
+            .component_ticks(entity_id, &T::component_type())
+            .is_some_and(|ticks| ticks.is_changed(last_run))
+    }
+}
+
+macro_rules! impl_change_filter_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: ChangeFilter),*> ChangeFilter for ($($name,)*) {
+            #[allow(unused_variables)]
+            fn matches(world: &World, entity_id: EntityId, last_run: u64) -> bool {
+                true $(&& $name::matches(world, entity_id, last_run))*
+            }
+        }
+    };
+}
+
+impl_change_filter_tuple!();
+impl_change_filter_tuple!(A);
+impl_change_filter_tuple!(A, B);
+impl_change_filter_tuple!(A, B, C);
+impl_change_filter_tuple!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Health, Position, Velocity};
+    use soroban_sdk::contract;
+
+    #[contract]
+    pub struct TestContract;
+
+    #[test]
+    fn test_component_ticks_follow_inserts_and_modifies() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity = world.spawn_empty().id();
+        let position = Position::component_type();
+        world.insert(entity, Position::new(0, 0));
+        assert_eq!(
+            world.component_ticks(entity, &position),
+            Some(ComponentTicks::new(1))
+        );
+
+        world.increment_change_tick();
+        world.insert(entity, Position::new(1, 0));
+        let ticks = world.component_ticks(entity, &position).unwrap();
+        assert_eq!((ticks.added, ticks.changed), (1, 2));
+
+        world.increment_change_tick();
+        world.modify(entity, &position, |_| {});
+        assert_eq!(world.component_ticks(entity, &position).unwrap().changed, 3);
+
+        world.remove::<Position>(entity);
+        assert_eq!(world.component_ticks(entity, &position), None);
+        assert_eq!(world.structure_tick(&position), 3);
+    }
+
+    #[test]
+    fn test_added_and_changed_filters() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.insert(a, Position::new(0, 0));
+        world.insert(b, Position::new(0, 0));
+        world.insert(b, Velocity::new(1, 1));
+        let last_run = world.change_tick();
+
+        world.increment_change_tick();
+        world.insert(a, Position::new(5, 5));
+        world.insert(a, Health::new(3));
+
+        let changed = world.query_filtered::<Position, Changed<Position>>(last_run);
+        assert_eq!(changed, alloc::vec![(a, Position::new(5, 5))]);
+        let added = world.query_filtered::<Position, Added<Position>>(last_run);
+        assert!(added.is_empty());
+        let both = world.query_filtered::<Position, (Changed<Position>, Added<Health>)>(last_run);
+        assert_eq!(both.len(), 1);
+        assert_eq!(world.query_filtered::<Position, ()>(last_run).len(), 2);
+        // Everything is new to a query that never ran
+        assert_eq!(
+            world.query_filtered::<Position, Added<Position>>(0).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_ticks_are_saved_and_dropped_on_despawn() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let mut world = World::new(&env);
+            let entity = world.spawn_empty().id();
+            world.increment_change_tick();
+            world.insert(entity, Position::new(0, 0));
+            world.save(&soroban_sdk::symbol_short!("world"));
+
+            let mut loaded = World::load(&env, &soroban_sdk::symbol_short!("world")).unwrap();
+            assert_eq!(loaded.change_tick(), 2);
+            assert_eq!(
+                loaded.component_ticks(entity, &Position::component_type()),
+                Some(ComponentTicks::new(2))
+            );
+            loaded.despawn(entity);
+            assert!(loaded.change_ticks.components.is_empty());
+        });
+    }
+}
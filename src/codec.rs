@@ -0,0 +1,421 @@
+use soroban_sdk::xdr::{FromXdr, ToXdr};
+use soroban_sdk::{Address, Bytes, BytesN, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec};
+
+/// Compact, length-checked binary encoding used for component, resource and
+/// event payloads
+///
+/// Integers are written big-endian at their natural width, `bool` and enum
+/// tags take one byte, and variable-length values are prefixed with a `u32`
+/// length. Decoding never panics on short input: it returns `None` instead.
+/// Host object types (`Address`, `Symbol`, `String`) are embedded as
+/// length-prefixed XDR.
+///
+/// This trait is implemented by `#[derive(Component)]`, `#[derive(Resource)]`
+/// and `#[derive(Event)]` for every field type, so nested user types only need
+/// one of those derives (or a manual impl) to be usable as fields.
+pub trait Codec: Sized {
+    /// Append the encoding of `self` to `out`
+    fn encode(&self, out: &mut Bytes);
+
+    /// Decode a value from the current position of `input`
+    fn decode(input: &mut Decoder) -> Option<Self>;
+}
+
+/// Cursor over encoded bytes
+pub struct Decoder<'a> {
+    data: &'a Bytes,
+    offset: u32,
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a decoder positioned at the start of `data`
+    pub fn new(data: &'a Bytes) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Get the environment the decoded data belongs to
+    pub fn env(&self) -> &Env {
+        self.data.env()
+    }
+
+    /// Number of bytes not yet consumed
+    pub fn remaining(&self) -> u32 {
+        self.data.len() - self.offset
+    }
+
+    /// Check if every byte has been consumed
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Consume the next `len` bytes
+    pub fn take(&mut self, len: u32) -> Option<Bytes> {
+        if len > self.remaining() {
+            return None;
+        }
+        let start = self.offset;
+        self.offset += len;
+        Some(self.data.slice(start..self.offset))
+    }
+
+    /// Consume the next `N` bytes into an array
+    pub fn take_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.take(N as u32)?;
+        let mut array = [0u8; N];
+        bytes.copy_into_slice(&mut array);
+        Some(array)
+    }
+
+    /// Consume a `u32` length prefix followed by that many bytes
+    pub fn take_prefixed(&mut self) -> Option<Bytes> {
+        let len = u32::decode(self)?;
+        self.take(len)
+    }
+}
+
+/// Encode a value into a fresh `Bytes`
+pub fn to_bytes<T: Codec>(env: &Env, value: &T) -> Bytes {
+    let mut out = Bytes::new(env);
+    value.encode(&mut out);
+    out
+}
+
+/// Decode a value that must span all of `data`
+///
+/// Returns `None` if `data` is too short or has trailing bytes.
+pub fn from_bytes<T: Codec>(data: &Bytes) -> Option<T> {
+    let mut input = Decoder::new(data);
+    let value = T::decode(&mut input)?;
+    if input.is_finished() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+macro_rules! impl_codec_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Codec for $ty {
+                fn encode(&self, out: &mut Bytes) {
+                    out.extend_from_array(&self.to_be_bytes());
+                }
+
+                fn decode(input: &mut Decoder) -> Option<Self> {
+                    Some(<$ty>::from_be_bytes(input.take_array()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl Codec for bool {
+    fn encode(&self, out: &mut Bytes) {
+        out.push_back(*self as u8);
+    }
+
+    fn decode(input: &mut Decoder) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl Codec for Bytes {
+    fn encode(&self, out: &mut Bytes) {
+        self.len().encode(out);
+        out.append(self);
+    }
+
+    fn decode(input: &mut Decoder) -> Option<Self> {
+        input.take_prefixed()
+    }
+}
+
+impl<const N: usize> Codec for BytesN<N> {
+    fn encode(&self, out: &mut Bytes) {
+        out.extend_from_array(&self.to_array());
+    }
+
+    fn decode(input: &mut Decoder) -> Option<Self> {
+        let array: [u8; N] = input.take_array()?;
+        Some(BytesN::from_array(input.env(), &array))
+    }
+}
+
+macro_rules! impl_codec_xdr {
+    ($($ty:ty),*) => {
+        $(
+            impl Codec for $ty {
+                fn encode(&self, out: &mut Bytes) {
+                    self.clone().to_xdr(out.env()).encode(out);
+                }
+
+                fn decode(input: &mut Decoder) -> Option<Self> {
+                    let xdr = input.take_prefixed()?;
+                    <$ty>::from_xdr(input.env(), &xdr).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_xdr!(Address, Symbol, String);
+
+impl<T> Codec for Vec<T>
+where
+    T: Codec + Clone + IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    fn encode(&self, out: &mut Bytes) {
+        self.len().encode(out);
+        for item in self.iter() {
+            item.encode(out);
+        }
+    }
+
+    fn decode(input: &mut Decoder) -> Option<Self> {
+        let len = u32::decode(input)?;
+        let remaining = input.remaining();
+        let mut items = Vec::new(input.env());
+        for _ in 0..len {
+            items.push_back(T::decode(input)?);
+            // Items that take no bytes would let a forged length spin for
+            // billions of iterations, so cap their count by the input size
+            if input.remaining() == remaining && len > remaining {
+                return None;
+            }
+        }
+        Some(items)
+    }
+}
+
+impl<T: Codec> Codec for Option<T> {
+    fn encode(&self, out: &mut Bytes) {
+        match self {
+            None => out.push_back(0),
+            Some(value) => {
+                out.push_back(1);
+                value.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Decoder) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => Some(None),
+            1 => Some(Some(T::decode(input)?)),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_codec_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Codec),+> Codec for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encode(&self, out: &mut Bytes) {
+                let ($($name,)+) = self;
+                $($name.encode(out);)+
+            }
+
+            fn decode(input: &mut Decoder) -> Option<Self> {
+                Some(($($name::decode(input)?,)+))
+            }
+        }
+    };
+}
+
+impl_codec_tuple!(A);
+impl_codec_tuple!(A, B);
+impl_codec_tuple!(A, B, C);
+impl_codec_tuple!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Component, Event, Resource};
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::{symbol_short, vec, Env};
+
+    #[derive(Clone, Debug, PartialEq, Eq, Component)]
+    #[component(name = "stats")]
+    struct Stats {
+        level: u8,
+        experience: i64,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Component)]
+    #[component(name = "owner", storage = "sparse")]
+    struct Owner {
+        address: Address,
+        tag: Symbol,
+        history: Vec<u32>,
+        stats: Stats,
+        nickname: Option<String>,
+    }
+
+    /// Fields named like the parameters of the generated code
+    #[derive(Clone, Debug, PartialEq, Eq, cougr_derive::Codec)]
+    struct Shadowed {
+        out: u32,
+        input: i8,
+        tag: bool,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, cougr_derive::Codec)]
+    enum ShadowedEnum {
+        Io { out: u32, input: i8 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Component)]
+    enum Shape {
+        Point,
+        Circle(u32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Resource)]
+    struct Round(u32);
+
+    #[derive(Clone, Debug, PartialEq, Eq, Event)]
+    #[event(name = "scored")]
+    struct Scored {
+        player: Address,
+        points: i32,
+    }
+
+    #[test]
+    fn test_integer_encoding_is_big_endian() {
+        let env = Env::default();
+        let bytes = to_bytes(&env, &(0x0102u16, -1i8, true));
+        assert_eq!(bytes, Bytes::from_array(&env, &[1, 2, 0xff, 1]));
+        assert_eq!(from_bytes(&bytes), Some((0x0102u16, -1i8, true)));
+    }
+
+    #[test]
+    fn test_decode_rejects_short_and_trailing_input() {
+        let env = Env::default();
+        let bytes = to_bytes(&env, &42u32);
+        assert_eq!(from_bytes::<u64>(&bytes), None);
+        assert_eq!(from_bytes::<u16>(&bytes), None);
+        assert_eq!(from_bytes::<bool>(&Bytes::from_array(&env, &[2])), None);
+    }
+
+    #[soroban_sdk::contracttype]
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Empty {
+        Unit,
+    }
+
+    impl Codec for Empty {
+        fn encode(&self, _out: &mut Bytes) {}
+
+        fn decode(_input: &mut Decoder) -> Option<Self> {
+            Some(Empty::Unit)
+        }
+    }
+
+    #[test]
+    fn test_decode_rejects_forged_length_of_empty_items() {
+        let env = Env::default();
+        let items = vec![&env, Empty::Unit, Empty::Unit];
+        let mut bytes = to_bytes(&env, &items);
+        bytes.extend_from_array(&[0, 0]);
+        let mut input = Decoder::new(&bytes);
+        assert_eq!(Vec::<Empty>::decode(&mut input), Some(items));
+
+        let forged = to_bytes(&env, &u32::MAX);
+        assert_eq!(from_bytes::<Vec<Empty>>(&forged), None);
+    }
+
+    #[test]
+    fn test_derive_component_roundtrip() {
+        use crate::component::{ComponentStorage, ComponentTrait};
+
+        let env = Env::default();
+        let owner = Owner {
+            address: Address::generate(&env),
+            tag: symbol_short!("knight"),
+            history: vec![&env, 1, 2, 3],
+            stats: Stats {
+                level: 7,
+                experience: -3,
+            },
+            nickname: Some(String::from_str(&env, "sir")),
+        };
+
+        assert_eq!(Owner::component_type(), symbol_short!("owner"));
+        assert_eq!(Owner::default_storage(), ComponentStorage::Sparse);
+        assert_eq!(Stats::default_storage(), ComponentStorage::Table);
+
+        let data = owner.serialize(&env);
+        assert_eq!(Owner::deserialize(&env, &data), Some(owner));
+
+        // Every strict prefix is rejected rather than panicking
+        for len in 0..data.len() {
+            assert_eq!(Owner::deserialize(&env, &data.slice(0..len)), None);
+        }
+    }
+
+    #[test]
+    fn test_derive_fields_named_like_parameters() {
+        let env = Env::default();
+        let shadowed = Shadowed {
+            out: 7,
+            input: -2,
+            tag: true,
+        };
+        let data = to_bytes(&env, &shadowed);
+        assert_eq!(from_bytes(&data), Some(shadowed));
+
+        let io = ShadowedEnum::Io { out: 1, input: 2 };
+        let data = to_bytes(&env, &io);
+        assert_eq!(from_bytes(&data), Some(io));
+    }
+
+    #[test]
+    fn test_derive_enum_roundtrip() {
+        use crate::component::ComponentTrait;
+
+        let env = Env::default();
+        assert_eq!(Shape::component_type(), symbol_short!("shape"));
+        for shape in [
+            Shape::Point,
+            Shape::Circle(5),
+            Shape::Rect {
+                width: 2,
+                height: 3,
+            },
+        ] {
+            let data = shape.serialize(&env);
+            assert_eq!(Shape::deserialize(&env, &data), Some(shape));
+        }
+        assert_eq!(
+            Shape::deserialize(&env, &Bytes::from_array(&env, &[9])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_derive_resource_and_event() {
+        use crate::event::EventTrait;
+        use crate::resource::ResourceTrait;
+
+        let env = Env::default();
+        assert_eq!(Round::resource_type(), symbol_short!("round"));
+        let data = Round(3).serialize(&env);
+        assert_eq!(Round::deserialize(&env, &data), Some(Round(3)));
+
+        let scored = Scored {
+            player: Address::generate(&env),
+            points: 10,
+        };
+        assert_eq!(Scored::event_type(), symbol_short!("scored"));
+        let data = scored.serialize(&env);
+        assert_eq!(Scored::deserialize(&env, &data), Some(scored));
+    }
+}
@@ -0,0 +1,304 @@
+use crate::component::{Component, ComponentTrait};
+use crate::entity::EntityId;
+use crate::event::{Event, EventTrait};
+use crate::resource::{Resource, ResourceTrait};
+use crate::system::{SystemMeta, SystemParam};
+use crate::world::World;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use soroban_sdk::{contracttype, Env, Symbol, Vec};
+
+/// A structural change to a world, recorded now and applied later
+///
+/// Entities are referenced by their packed [`EntityId::to_bits`] value so the
+/// whole queue is a plain Soroban value that can be stored or passed between
+/// contracts. An entity spawned by the queue is referenced by later commands
+/// through the placeholder returned by [`Commands::spawn`], which applying
+/// the queue replaces with the spawned entity's ID.
+#[contracttype]
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Spawn an entity with the given components, standing in for the
+    /// placeholder bits until applied
+    Spawn(u64, Vec<Component>),
+    /// Despawn an entity
+    Despawn(u64),
+    /// Insert a component on an entity, replacing any existing value
+    Insert(u64, Component),
+    /// Remove a component type from an entity
+    Remove(u64, Symbol),
+    /// Insert a resource, replacing any existing one of the same type
+    InsertResource(Resource),
+    /// Send an event
+    SendEvent(Event),
+}
+
+impl Command {
+    /// Apply this command to `world`
+    ///
+    /// Commands targeting entities that no longer exist are ignored.
+    pub fn apply(self, world: &mut World) {
+        self.apply_spawned(world, &mut BTreeMap::new());
+    }
+
+    /// Apply this command, resolving placeholders through `spawned`, which
+    /// maps the placeholder bits of each entity spawned so far to its ID
+    fn apply_spawned(self, world: &mut World, spawned: &mut BTreeMap<u64, EntityId>) {
+        let resolve = |bits: u64| {
+            spawned
+                .get(&bits)
+                .copied()
+                .unwrap_or_else(|| EntityId::from_bits(bits))
+        };
+        match self {
+            Command::Spawn(placeholder, components) => {
+                let entity = world.spawn(components);
+                spawned.insert(placeholder, entity.id());
+            }
+            Command::Despawn(bits) => {
+                world.despawn(resolve(bits));
+            }
+            Command::Insert(bits, component) => {
+                world.add_component_to_entity(resolve(bits), component);
+            }
+            Command::Remove(bits, component_type) => {
+                let entity_id = resolve(bits);
+                if world.exists(entity_id) {
+                    world.remove_component_from_entity(entity_id, &component_type);
+                }
+            }
+            Command::InsertResource(resource) => world.add_resource(resource),
+            Command::SendEvent(event) => world.send_event(event),
+        }
+    }
+}
+
+/// Queue of [`Command`]s recorded by a system
+///
+/// As a system parameter, the queue is applied right after the system
+/// returns, so structural changes never happen while the system is still
+/// iterating. Outside a system, call [`Commands::apply`] at a convenient
+/// sync point.
+pub struct Commands {
+    env: Env,
+    queue: Rc<RefCell<Vec<Command>>>,
+    spawned: u32,
+}
+
+impl Commands {
+    /// Create an empty queue
+    pub fn new(env: &Env) -> Self {
+        Self {
+            env: env.clone(),
+            queue: Rc::new(RefCell::new(Vec::new(env))),
+            spawned: 0,
+        }
+    }
+
+    /// Record a command
+    pub fn push(&mut self, command: Command) {
+        self.queue.borrow_mut().push_back(command);
+    }
+
+    /// Spawn an entity with the given components
+    ///
+    /// Returns a placeholder ID that later commands in this queue can target,
+    /// such as [`Commands::insert`]. The placeholder has ID 0, so it never
+    /// names an existing entity; applying the queue swaps it for the spawned
+    /// entity's ID.
+    pub fn spawn(&mut self, components: Vec<Component>) -> EntityId {
+        self.spawned += 1;
+        let placeholder = EntityId::new(0, self.spawned);
+        self.push(Command::Spawn(placeholder.to_bits(), components));
+        placeholder
+    }
+
+    /// Despawn an entity
+    pub fn despawn(&mut self, entity_id: EntityId) {
+        self.push(Command::Despawn(entity_id.to_bits()));
+    }
+
+    /// Insert a typed component on an entity
+    pub fn insert<T: ComponentTrait>(&mut self, entity_id: EntityId, value: T) {
+        let component = Component::with_storage(
+            T::component_type(),
+            value.serialize(&self.env),
+            T::default_storage(),
+        );
+        self.push(Command::Insert(entity_id.to_bits(), component));
+    }
+
+    /// Remove a typed component from an entity
+    pub fn remove<T: ComponentTrait>(&mut self, entity_id: EntityId) {
+        self.push(Command::Remove(entity_id.to_bits(), T::component_type()));
+    }
+
+    /// Insert a typed resource, replacing any existing one
+    pub fn insert_resource<T: ResourceTrait>(&mut self, value: T) {
+        let resource = Resource::new(T::resource_type(), value.serialize(&self.env));
+        self.push(Command::InsertResource(resource));
+    }
+
+    /// Send a typed event
+    pub fn send_event<E: EventTrait>(&mut self, event: E) {
+        let event = Event::new(E::event_type(), event.serialize(&self.env));
+        self.push(Command::SendEvent(event));
+    }
+
+    /// Get the recorded commands
+    pub fn commands(&self) -> Vec<Command> {
+        self.queue.borrow().clone()
+    }
+
+    /// Get the number of recorded commands
+    pub fn len(&self) -> usize {
+        self.queue.borrow().len() as usize
+    }
+
+    /// Check if no commands have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.queue.borrow().is_empty()
+    }
+
+    /// Apply every recorded command in order and clear the queue
+    pub fn apply(&mut self, world: &mut World) {
+        apply_queue(&self.queue, world);
+    }
+}
+
+fn apply_queue(queue: &RefCell<Vec<Command>>, world: &mut World) {
+    let commands = queue.replace(Vec::new(world.env()));
+    let mut spawned = BTreeMap::new();
+    for command in commands {
+        command.apply_spawned(world, &mut spawned);
+    }
+}
+
+impl SystemParam for Commands {
+    type Local = ();
+    type State = Rc<RefCell<Vec<Command>>>;
+
+    fn fetch(world: &World, _local: &mut (), _system: &SystemMeta) -> (Self, Self::State) {
+        let commands = Commands::new(world.env());
+        let queue = commands.queue.clone();
+        (commands, queue)
+    }
+
+    fn apply(state: Self::State, world: &mut World) {
+        apply_queue(&state, world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Position, Velocity};
+    use crate::event::DamageEvent;
+    use crate::resource::GameState;
+    use crate::system::{IntoSystem, QueryParam, System};
+    use soroban_sdk::{contract, symbol_short, vec};
+
+    #[contract]
+    pub struct TestContract;
+
+    #[test]
+    fn test_commands_are_deferred_until_apply() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity = world.spawn_empty().id();
+
+        let mut commands = Commands::new(&env);
+        commands.insert(entity, Position::new(1, 2));
+        commands.spawn(vec![&env]);
+        commands.insert_resource(GameState::new());
+        commands.send_event(DamageEvent::new(entity.to_bits(), 3, symbol_short!("fire")));
+        assert_eq!(commands.len(), 4);
+        assert!(!world.has::<Position>(entity));
+        assert_eq!(world.entity_count(), 1);
+
+        commands.apply(&mut world);
+        assert!(commands.is_empty());
+        assert_eq!(world.get::<Position>(entity), Some(Position::new(1, 2)));
+        assert_eq!(world.entity_count(), 2);
+        assert!(world.contains_resource::<GameState>());
+        assert_eq!(world.get_events(&DamageEvent::event_type()).len(), 1);
+
+        // Replaces rather than duplicates the resource
+        commands.insert_resource(GameState::new());
+        commands.remove::<Position>(entity);
+        commands.despawn(entity);
+        commands.apply(&mut world);
+        assert_eq!(world.resources.len(), 1);
+        assert!(!world.exists(entity));
+    }
+
+    #[test]
+    fn test_commands_target_spawned_entities() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world.spawn_empty();
+
+        let mut commands = Commands::new(&env);
+        let first = commands.spawn(vec![&env]);
+        let second = commands.spawn(vec![&env]);
+        assert_ne!(first, second);
+        assert!(!world.exists(first));
+        commands.insert(first, Position::new(1, 2));
+        commands.insert(second, Velocity::new(3, 4));
+        commands.remove::<Velocity>(second);
+        commands.apply(&mut world);
+
+        let positions = world.query::<Position>();
+        assert_eq!(positions.len(), 1);
+        let (spawned, position) = &positions[0];
+        assert_ne!(spawned.id(), 0);
+        assert_eq!(*position, Position::new(1, 2));
+        assert_eq!(world.entity_count(), 3);
+        assert!(world.query::<Velocity>().is_empty());
+    }
+
+    #[test]
+    fn test_despawn_while_iterating_in_system() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        for x in 0..4 {
+            let entity = world.spawn_empty().id();
+            world.insert(entity, Position::new(x, 0));
+            world.insert(entity, Velocity::new(1, 0));
+        }
+
+        let mut system = (|query: QueryParam<Position>, mut commands: Commands| {
+            for (entity_id, position) in query.iter() {
+                if position.x % 2 == 0 {
+                    commands.despawn(entity_id);
+                }
+            }
+        })
+        .into_system();
+        system.run(&mut world, ());
+
+        assert_eq!(world.entity_count(), 2);
+        assert_eq!(world.query::<Position>().len(), 2);
+    }
+
+    #[test]
+    fn test_command_queue_is_storable() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let mut commands = Commands::new(&env);
+        commands.despawn(EntityId::new(1, 2));
+        commands.remove::<Velocity>(EntityId::new(3, 0));
+
+        env.as_contract(&contract_id, || {
+            let key = symbol_short!("pending");
+            env.storage().instance().set(&key, &commands.commands());
+            let stored: Vec<Command> = env.storage().instance().get(&key).unwrap();
+            assert_eq!(stored.len(), 2);
+            assert!(matches!(
+                stored.get(0),
+                Some(Command::Despawn(bits)) if bits == EntityId::new(1, 2).to_bits()
+            ));
+        });
+    }
+}
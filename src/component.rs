@@ -1,7 +1,6 @@
+use crate::hooks::ComponentHooks;
 use alloc::vec::Vec;
-use soroban_sdk::{
-    contracttype, symbol_short, Bytes, Env, FromVal, IntoVal, Symbol, TryFromVal, Val,
-};
+use soroban_sdk::{contracttype, Bytes, Env, IntoVal, Symbol, TryFromVal, Val};
 
 /// A unique identifier for a component type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -35,16 +34,12 @@ impl TryFromVal<Env, Val> for ComponentId {
 
 #[contracttype]
 #[repr(u32)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ComponentStorage {
+    #[default]
     Table = 0,
     Sparse = 1,
 }
-impl Default for ComponentStorage {
-    fn default() -> Self {
-        Self::Table
-    }
-}
 
 #[contracttype]
 #[derive(Debug, Clone)]
@@ -91,6 +86,11 @@ impl Component {
 pub struct ComponentRegistry {
     next_id: u32,
     components: Vec<(Symbol, ComponentId)>,
+    /// Lifecycle hooks per component type
+    ///
+    /// Not saved: hooks are functions, so contracts register them on every
+    /// invocation.
+    hooks: Vec<(Symbol, ComponentHooks)>,
 }
 
 impl ComponentRegistry {
@@ -99,6 +99,7 @@ impl ComponentRegistry {
         Self {
             next_id: 1,
             components: Vec::new(),
+            hooks: Vec::new(),
         }
     }
 
@@ -151,6 +152,38 @@ impl ComponentRegistry {
         }
         false
     }
+
+    /// Get the lifecycle hooks of a component type, registering it if needed
+    pub fn hooks_mut(&mut self, component_type: Symbol) -> &mut ComponentHooks {
+        let index = match self
+            .hooks
+            .iter()
+            .position(|(ctype, _)| ctype == &component_type)
+        {
+            Some(index) => index,
+            None => {
+                self.register_component(component_type.clone());
+                self.hooks.push((component_type, ComponentHooks::default()));
+                self.hooks.len() - 1
+            }
+        };
+        &mut self.hooks[index].1
+    }
+
+    /// Get the lifecycle hooks of a component type, if any were registered
+    pub fn hooks(&self, component_type: &Symbol) -> Option<ComponentHooks> {
+        for (ctype, hooks) in &self.hooks {
+            if ctype == component_type {
+                return Some(*hooks);
+            }
+        }
+        None
+    }
+
+    /// Check if any component type has lifecycle hooks
+    pub fn has_hooks(&self) -> bool {
+        self.hooks.iter().any(|(_, hooks)| !hooks.is_empty())
+    }
 }
 
 impl Default for ComponentRegistry {
@@ -159,6 +192,35 @@ impl Default for ComponentRegistry {
     }
 }
 
+// Soroban SDK trait implementations for ComponentRegistry
+impl IntoVal<Env, Val> for ComponentRegistry {
+    fn into_val(&self, env: &Env) -> Val {
+        let mut components = soroban_sdk::Vec::<(Symbol, u32)>::new(env);
+        for (ctype, id) in &self.components {
+            components.push_back((ctype.clone(), id.id()));
+        }
+        (self.next_id, components).into_val(env)
+    }
+}
+
+impl TryFromVal<Env, Val> for ComponentRegistry {
+    type Error = soroban_sdk::ConversionError;
+
+    fn try_from_val(env: &Env, val: &Val) -> Result<Self, Self::Error> {
+        let (next_id, stored): (u32, soroban_sdk::Vec<(Symbol, u32)>) =
+            TryFromVal::try_from_val(env, val)?;
+        let mut components = Vec::new();
+        for (ctype, id) in stored.iter() {
+            components.push((ctype, ComponentId::new(id)));
+        }
+        Ok(ComponentRegistry {
+            next_id,
+            components,
+            hooks: Vec::new(),
+        })
+    }
+}
+
 pub trait ComponentTrait {
     fn component_type() -> Symbol;
     fn serialize(&self, env: &Env) -> Bytes;
@@ -170,96 +232,13 @@ pub trait ComponentTrait {
     }
 }
 
-#[contracttype]
-#[derive(Clone)]
-pub struct Position {
-    pub x: i32,
-    pub y: i32,
-}
-impl Position {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-impl ComponentTrait for Position {
-    fn component_type() -> Symbol {
-        symbol_short!("position")
-    }
-    fn serialize(&self, env: &Env) -> Bytes {
-        let mut bytes = Bytes::new(env);
-        let x_bytes = Bytes::from_array(env, &self.x.to_be_bytes());
-        let y_bytes = Bytes::from_array(env, &self.y.to_be_bytes());
-        bytes.append(&x_bytes);
-        bytes.append(&y_bytes);
-        bytes
-    }
-    fn deserialize(env: &Env, data: &Bytes) -> Option<Self> {
-        if data.len() != 8 {
-            return None;
-        }
-        let x = i32::from_be_bytes([
-            data.get(0).unwrap(),
-            data.get(1).unwrap(),
-            data.get(2).unwrap(),
-            data.get(3).unwrap(),
-        ]);
-        let y = i32::from_be_bytes([
-            data.get(4).unwrap(),
-            data.get(5).unwrap(),
-            data.get(6).unwrap(),
-            data.get(7).unwrap(),
-        ]);
-        Some(Self { x, y })
-    }
-}
-
-#[contracttype]
-#[derive(Clone)]
-pub struct Velocity {
-    pub x: i32,
-    pub y: i32,
-}
-impl Velocity {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-impl ComponentTrait for Velocity {
-    fn component_type() -> Symbol {
-        symbol_short!("velocity")
-    }
-    fn serialize(&self, env: &Env) -> Bytes {
-        let mut bytes = Bytes::new(env);
-        let x_bytes = Bytes::from_array(env, &self.x.to_be_bytes());
-        let y_bytes = Bytes::from_array(env, &self.y.to_be_bytes());
-        bytes.append(&x_bytes);
-        bytes.append(&y_bytes);
-        bytes
-    }
-    fn deserialize(env: &Env, data: &Bytes) -> Option<Self> {
-        if data.len() != 8 {
-            return None;
-        }
-        let x = i32::from_be_bytes([
-            data.get(0).unwrap(),
-            data.get(1).unwrap(),
-            data.get(2).unwrap(),
-            data.get(3).unwrap(),
-        ]);
-        let y = i32::from_be_bytes([
-            data.get(4).unwrap(),
-            data.get(5).unwrap(),
-            data.get(6).unwrap(),
-            data.get(7).unwrap(),
-        ]);
-        Some(Self { x, y })
-    }
-}
+// The standard components used to live here; keep the old paths working
+pub use crate::components::{Collider, Health, Position, Velocity};
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::Env;
+    use soroban_sdk::{symbol_short, Env};
 
     #[test]
     fn test_component_id_creation() {
@@ -1,8 +1,257 @@
-use soroban_sdk::contracttype;
+//! Standard gameplay components
+//!
+//! Spatial components share one fixed-point convention: values are signed
+//! integers counting thousandths of a unit, so `Position::new(UNIT, 0)` is one
+//! unit to the right of the origin and `Rotation::new(UNIT)` is one radian.
+//! Integer units keep simulation deterministic across validators.
 
+use cougr_derive::Component;
+use soroban_sdk::{contracttype, Address};
+
+/// Fixed-point scale shared by the spatial components: one whole unit
+pub const UNIT: i32 = 1000;
+
+/// Location of an entity, in thousandths of a unit
 #[contracttype]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
 pub struct Position {
-    pub x: u32,
-    pub y: u32,
+    pub x: i32,
+    pub y: i32,
+}
+impl Position {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Change in [`Position`] per tick, in thousandths of a unit
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Velocity {
+    pub x: i32,
+    pub y: i32,
+}
+impl Velocity {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Heading of an entity, in thousandths of a radian
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Rotation {
+    pub angle: i32,
+}
+impl Rotation {
+    pub fn new(angle: i32) -> Self {
+        Self { angle }
+    }
+}
+
+/// Size multiplier of an entity, where [`UNIT`] is the original size
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq, Component)]
+pub struct Scale {
+    pub x: i32,
+    pub y: i32,
+}
+impl Scale {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+    pub fn uniform(scale: i32) -> Self {
+        Self::new(scale, scale)
+    }
+}
+impl Default for Scale {
+    fn default() -> Self {
+        Self::uniform(UNIT)
+    }
+}
+
+/// Axis-aligned box centred on an entity's [`Position`]
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Collider {
+    pub half_width: i32,
+    pub half_height: i32,
+}
+impl Collider {
+    pub fn new(half_width: i32, half_height: i32) -> Self {
+        Self {
+            half_width,
+            half_height,
+        }
+    }
+    /// Check if this box at `position` overlaps `other` at `other_position`
+    ///
+    /// Boxes that only touch along an edge do not overlap.
+    pub fn overlaps(
+        &self,
+        position: &Position,
+        other: &Collider,
+        other_position: &Position,
+    ) -> bool {
+        let dx = (position.x as i64 - other_position.x as i64).abs();
+        let dy = (position.y as i64 - other_position.y as i64).abs();
+        dx < self.half_width as i64 + other.half_width as i64
+            && dy < self.half_height as i64 + other.half_height as i64
+    }
+}
+
+/// Hit points of an entity, kept between zero and `max`
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+impl Health {
+    pub fn new(max: i32) -> Self {
+        Self { current: max, max }
+    }
+    /// Apply `amount` of damage; negative amounts heal
+    ///
+    /// A negative `max`, as a decoded value may have, is treated as zero.
+    pub fn damage(&mut self, amount: i32) {
+        let max = self.max.max(0);
+        self.current = self.current.saturating_sub(amount).max(0).min(max);
+    }
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0
+    }
+}
+
+/// Number of ticks an entity has left to live
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Lifetime {
+    pub remaining: u32,
+}
+impl Lifetime {
+    pub fn new(ticks: u32) -> Self {
+        Self { remaining: ticks }
+    }
+    /// Count down one tick, returning `true` once the lifetime has run out
+    pub fn tick(&mut self) -> bool {
+        self.remaining = self.remaining.saturating_sub(1);
+        self.is_expired()
+    }
+    pub fn is_expired(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Account controlling an entity
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq, Component)]
+pub struct Owner(pub Address);
+
+/// Side an entity plays for
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Team(pub u32);
+
+/// Points earned by an entity
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+pub struct Score(pub i64);
+impl Score {
+    pub fn add(&mut self, points: i64) {
+        self.0 = self.0.saturating_add(points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::ComponentTrait;
+    use crate::world::World;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_components_roundtrip_through_world() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let owner = Address::generate(&env);
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Position::new(UNIT, -UNIT));
+        world.insert(entity, Rotation::new(1571));
+        world.insert(entity, Scale::default());
+        world.insert(entity, Owner(owner.clone()));
+        world.insert(entity, Team(2));
+        world.insert(entity, Score(40));
+
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(Position::new(1000, -1000))
+        );
+        assert_eq!(world.get::<Rotation>(entity), Some(Rotation::new(1571)));
+        assert_eq!(world.get::<Scale>(entity), Some(Scale::uniform(UNIT)));
+        assert_eq!(world.get::<Owner>(entity), Some(Owner(owner)));
+        assert_eq!(world.get::<Team>(entity), Some(Team(2)));
+        assert_eq!(world.get::<Score>(entity), Some(Score(40)));
+    }
+
+    #[test]
+    fn test_component_types_are_distinct() {
+        let types = [
+            Position::component_type(),
+            Velocity::component_type(),
+            Rotation::component_type(),
+            Scale::component_type(),
+            Collider::component_type(),
+            Health::component_type(),
+            Lifetime::component_type(),
+            Owner::component_type(),
+            Team::component_type(),
+            Score::component_type(),
+        ];
+        for (i, a) in types.iter().enumerate() {
+            for b in &types[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_collider_overlap() {
+        let collider = Collider::new(2, 2);
+        let origin = Position::new(0, 0);
+        assert!(collider.overlaps(&origin, &collider, &Position::new(3, -3)));
+        assert!(!collider.overlaps(&origin, &collider, &Position::new(4, 0)));
+        assert!(!collider.overlaps(&origin, &collider, &Position::new(0, 5)));
+    }
+
+    #[test]
+    fn test_health_is_clamped() {
+        let mut health = Health::new(10);
+        health.damage(-5);
+        assert_eq!(health.current, 10);
+        health.damage(25);
+        assert_eq!(health.current, 0);
+        assert!(health.is_dead());
+    }
+
+    #[test]
+    fn test_health_with_negative_max() {
+        let mut health = Health {
+            current: 5,
+            max: -3,
+        };
+        health.damage(1);
+        assert_eq!(health.current, 0);
+        assert!(health.is_dead());
+    }
+
+    #[test]
+    fn test_lifetime_expires() {
+        let mut lifetime = Lifetime::new(2);
+        assert!(!lifetime.tick());
+        assert!(lifetime.tick());
+        assert!(lifetime.tick());
+        assert_eq!(lifetime.remaining, 0);
+    }
 }
@@ -0,0 +1,227 @@
+use crate::event::EventTrait;
+use crate::resource::ResourceTrait;
+use crate::world::World;
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use soroban_sdk::Symbol;
+
+/// A type-erased run condition stored in a [`Schedule`](crate::scheduler::Schedule)
+pub type BoxedCondition = Box<dyn Condition>;
+
+/// A check deciding whether a scheduled system runs this tick
+///
+/// Implemented for every `FnMut(&World) -> bool`. Conditions are evaluated
+/// just before their system would run, so they see the effects of systems
+/// earlier in the schedule. The world is mutable so that conditions such as
+/// [`on_event`] can save what they have seen with it.
+pub trait Condition {
+    /// Check the condition against the world
+    fn evaluate(&mut self, world: &mut World) -> bool;
+
+    /// Condition that holds when both `self` and `other` hold
+    ///
+    /// `other` is not evaluated if `self` fails.
+    fn and<C: Condition>(self, other: C) -> And<Self, C>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Condition that holds when either `self` or `other` holds
+    ///
+    /// `other` is not evaluated if `self` holds.
+    fn or<C: Condition>(self, other: C) -> Or<Self, C>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+}
+
+impl<F> Condition for F
+where
+    F: FnMut(&World) -> bool,
+{
+    fn evaluate(&mut self, world: &mut World) -> bool {
+        self(world)
+    }
+}
+
+/// Condition combining two conditions with a logical and
+pub struct And<A, B>(A, B);
+
+impl<A: Condition, B: Condition> Condition for And<A, B> {
+    fn evaluate(&mut self, world: &mut World) -> bool {
+        self.0.evaluate(world) && self.1.evaluate(world)
+    }
+}
+
+/// Condition combining two conditions with a logical or
+pub struct Or<A, B>(A, B);
+
+impl<A: Condition, B: Condition> Condition for Or<A, B> {
+    fn evaluate(&mut self, world: &mut World) -> bool {
+        self.0.evaluate(world) || self.1.evaluate(world)
+    }
+}
+
+/// Condition negating another condition
+pub struct Not<C>(C);
+
+impl<C: Condition> Condition for Not<C> {
+    fn evaluate(&mut self, world: &mut World) -> bool {
+        !self.0.evaluate(world)
+    }
+}
+
+/// Condition that holds when `condition` does not
+pub fn not<C: Condition>(condition: C) -> Not<C> {
+    Not(condition)
+}
+
+/// Condition that holds when the world has a resource of type `T`
+pub fn resource_exists<T: ResourceTrait>() -> impl Condition {
+    |world: &World| world.contains_resource::<T>()
+}
+
+/// Condition that holds when the resource of type `T` decodes to `value`
+///
+/// Fails if the resource is missing.
+pub fn resource_equals<T: ResourceTrait + PartialEq>(value: T) -> impl Condition {
+    move |world: &World| {
+        world
+            .resource::<T>()
+            .is_some_and(|current| current == value)
+    }
+}
+
+/// Condition that holds when the resource of type `T` exists and satisfies
+/// `predicate`
+pub fn resource_matches<T, F>(mut predicate: F) -> impl Condition
+where
+    T: ResourceTrait,
+    F: FnMut(&T) -> bool,
+{
+    move |world: &World| {
+        world
+            .resource::<T>()
+            .is_some_and(|current| predicate(&current))
+    }
+}
+
+/// Condition that holds when an event of type `E` was sent since the last
+/// time it was evaluated
+///
+/// The condition reads events as `reader`, keeping its cursor in the world,
+/// so events seen in one contract invocation are not seen again in the next.
+/// Readers must be unique per event type.
+pub fn on_event<E: EventTrait>(reader: Symbol) -> OnEvent<E> {
+    OnEvent {
+        reader,
+        _marker: PhantomData,
+    }
+}
+
+/// Condition returned by [`on_event`]
+pub struct OnEvent<E> {
+    reader: Symbol,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<E: EventTrait> Condition for OnEvent<E> {
+    fn evaluate(&mut self, world: &mut World) -> bool {
+        !world.read_events_as::<E>(&self.reader).is_empty()
+    }
+}
+
+/// Condition that holds on every `n`th tick, starting with tick 0
+///
+/// Uses [`World::tick`], which is saved with the world, so the cadence holds
+/// across contract invocations even though schedules are rebuilt each time.
+pub fn every_n_ticks(n: u64) -> impl Condition {
+    assert!(n > 0, "every_n_ticks requires n > 0");
+    move |world: &World| world.tick() % n == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{DamageEvent, Event};
+    use crate::resource::GameState;
+    use soroban_sdk::{contract, symbol_short, Env};
+
+    #[contract]
+    pub struct TestContract;
+
+    fn always(_world: &World) -> bool {
+        true
+    }
+
+    #[test]
+    fn test_combinators() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+
+        assert!(always.and(always).evaluate(&mut world));
+        assert!(!always.and(not(always)).evaluate(&mut world));
+        assert!(not(always).or(always).evaluate(&mut world));
+        assert!(!not(always.or(always)).evaluate(&mut world));
+    }
+
+    #[test]
+    fn test_builtin_conditions() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let mut exists = resource_exists::<GameState>();
+        let mut running = resource_matches::<GameState, _>(|state| !state.is_game_over);
+        let mut fresh = resource_equals(GameState::new());
+        let mut damaged = on_event::<DamageEvent>(symbol_short!("damaged"));
+        let mut every_third = every_n_ticks(3);
+
+        assert!(!exists.evaluate(&mut world));
+        assert!(!running.evaluate(&mut world));
+        assert!(!fresh.evaluate(&mut world));
+        assert!(!damaged.evaluate(&mut world));
+
+        world.insert_resource(GameState::new());
+        assert!(exists.evaluate(&mut world));
+        assert!(running.evaluate(&mut world));
+        assert!(fresh.evaluate(&mut world));
+
+        let event = DamageEvent::new(1, 5, symbol_short!("fire"));
+        let data = event.serialize(&env);
+        world.send_event(Event::new(DamageEvent::event_type(), data));
+        assert!(damaged.evaluate(&mut world));
+        assert!(!damaged.evaluate(&mut world));
+
+        let mut hits = 0;
+        for _ in 0..7 {
+            if every_third.evaluate(&mut world) {
+                hits += 1;
+            }
+            world.increment_tick();
+        }
+        assert_eq!(hits, 3);
+    }
+
+    #[test]
+    fn test_on_event_survives_save_and_load() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let mut world = World::new(&env);
+            world.send(DamageEvent::new(1, 5, symbol_short!("fire")));
+            assert!(on_event::<DamageEvent>(symbol_short!("damaged")).evaluate(&mut world));
+            world.update_events();
+            world.save(&symbol_short!("world"));
+
+            // A rebuilt condition does not see the buffered event again
+            let mut world = World::load(&env, &symbol_short!("world")).unwrap();
+            let mut damaged = on_event::<DamageEvent>(symbol_short!("damaged"));
+            assert!(!damaged.evaluate(&mut world));
+            world.send(DamageEvent::new(1, 2, symbol_short!("fire")));
+            assert!(damaged.evaluate(&mut world));
+        });
+    }
+}
@@ -1,5 +1,10 @@
-use core::fmt;
-use soroban_sdk::{Env, FromVal, IntoVal, Symbol, TryFromVal, Val, Vec};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use soroban_sdk::{Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
+
+/// Largest ID an [`EntityManager`] hands out, so IDs pack into
+/// [`EntityId::to_bits`]
+pub const MAX_ENTITY_ID: u64 = u32::MAX as u64;
 
 /// A unique identifier for an entity in the ECS world
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -28,6 +33,24 @@ impl EntityId {
     pub fn is_valid(&self) -> bool {
         self.id != 0
     }
+
+    /// Pack this ID into a single `u64` for passing across contract boundaries
+    ///
+    /// The generation occupies the high 32 bits and the ID the low 32 bits;
+    /// IDs handed out by [`EntityManager`] always fit, as it never allocates
+    /// past [`MAX_ENTITY_ID`].
+    pub fn to_bits(&self) -> u64 {
+        assert!(
+            self.id <= MAX_ENTITY_ID,
+            "entity id does not fit in 32 bits"
+        );
+        ((self.generation as u64) << 32) | self.id
+    }
+
+    /// Unpack an ID produced by [`EntityId::to_bits`]
+    pub fn from_bits(bits: u64) -> Self {
+        Self::new(bits & 0xFFFF_FFFF, (bits >> 32) as u32)
+    }
 }
 
 // Soroban SDK trait implementations for EntityId
@@ -55,11 +78,10 @@ pub struct Entity {
 
 impl Entity {
     /// Create a new entity
-    pub fn new(id: EntityId) -> Self {
-        let env = soroban_sdk::Env::default();
+    pub fn new(env: &Env, id: EntityId) -> Self {
         Self {
             id,
-            component_types: Vec::new(&env),
+            component_types: Vec::new(env),
         }
     }
 
@@ -76,7 +98,7 @@ impl Entity {
     /// Remove a component type from this entity
     pub fn remove_component_type(&mut self, component_type: &Symbol) -> bool {
         let mut found = false;
-        let mut new_components = Vec::new(&soroban_sdk::Env::default());
+        let mut new_components = Vec::new(self.component_types.env());
 
         for i in 0..self.component_types.len() {
             let ctype = self.component_types.get(i).unwrap();
@@ -145,71 +167,80 @@ impl TryFromVal<Env, Val> for Entity {
 }
 
 /// Manager for handling entity lifecycle
+///
+/// Despawned IDs are recycled with a bumped generation, so handles to a
+/// despawned entity never match the entity that reuses its slot. Entities are
+/// keyed by ID, so looking one up or validating a handle is a single map
+/// lookup rather than a scan.
 #[derive(Debug, Clone)]
 pub struct EntityManager {
     next_id: u64,
-    entities: Vec<Entity>,
+    /// Live entities, keyed by ID
+    entities: Map<u64, Entity>,
     free_list: Vec<u64>,
+    /// Current generation of each ID slot, indexed by `id - 1`
+    generations: Vec<u32>,
 }
 
 impl EntityManager {
     /// Create a new entity manager
-    pub fn new() -> Self {
-        let env = soroban_sdk::Env::default();
+    pub fn new(env: &Env) -> Self {
         Self {
             next_id: 1,
-            entities: Vec::new(&env),
-            free_list: Vec::new(&env),
+            entities: Map::new(env),
+            free_list: Vec::new(env),
+            generations: Vec::new(env),
         }
     }
 
     /// Spawn a new entity
+    ///
+    /// Panics once [`MAX_ENTITY_ID`] IDs are live at the same time; despawned
+    /// IDs are reused first.
     pub fn spawn(&mut self) -> EntityId {
-        let id = if self.free_list.len() > 0 {
-            let freed_id = self.free_list.get(self.free_list.len() - 1).unwrap();
-            self.free_list.remove(self.free_list.len() - 1);
-            freed_id
+        let entity_id = if let Some(freed_id) = self.free_list.pop_back() {
+            let slot = (freed_id - 1) as u32;
+            let generation = self.generations.get(slot).unwrap().wrapping_add(1);
+            self.generations.set(slot, generation);
+            EntityId::new(freed_id, generation)
         } else {
             let id = self.next_id;
+            assert!(id <= MAX_ENTITY_ID, "entity ids exhausted");
             self.next_id += 1;
-            id
+            self.generations.push_back(0);
+            EntityId::new(id, 0)
         };
 
-        let entity_id = EntityId::new(id, 0);
-        let entity = Entity::new(entity_id);
-        self.entities.push_back(entity);
+        let entity = Entity::new(self.entities.env(), entity_id);
+        self.entities.set(entity_id.id(), entity);
         entity_id
     }
 
     /// Despawn an entity
     pub fn despawn(&mut self, entity_id: EntityId) -> bool {
-        for i in 0..self.entities.len() {
-            let entity = self.entities.get(i).unwrap();
-            if entity.id() == entity_id {
-                self.entities.remove(i);
-                self.free_list.push_back(entity_id.id());
-                return true;
-            }
+        if !self.exists(entity_id) {
+            return false;
         }
-        false
+        self.entities.remove(entity_id.id());
+        self.free_list.push_back(entity_id.id());
+        true
     }
 
     /// Get an entity by ID
     pub fn get_entity(&self, entity_id: EntityId) -> Option<Entity> {
-        for i in 0..self.entities.len() {
-            let entity = self.entities.get(i).unwrap();
-            if entity.id() == entity_id {
-                return Some(entity.clone());
-            }
-        }
-        None
+        let entity = self.entities.get(entity_id.id())?;
+        (entity.id() == entity_id).then_some(entity)
     }
 
-    /// Get a mutable reference to an entity by ID
-    pub fn get_entity_mut(&mut self, entity_id: EntityId) -> Option<Entity> {
-        // Since soroban_sdk::Vec doesn't have get_mut, we'll need to restructure this
-        // For now, return a clone - this is a limitation of the Soroban SDK
-        self.get_entity(entity_id)
+    /// Get a mutable handle to an entity by ID
+    ///
+    /// Changes made through the handle are written back when it is dropped.
+    pub fn get_entity_mut(&mut self, entity_id: EntityId) -> Option<EntityMut<'_>> {
+        let entity = self.get_entity(entity_id)?;
+        Some(EntityMut {
+            entities: &mut self.entities,
+            entity,
+        })
     }
 
     /// Get the total number of entities
@@ -217,65 +248,108 @@ impl EntityManager {
         self.entities.len().try_into().unwrap()
     }
 
+    /// Get the current generation of an ID slot, if it was ever allocated
+    pub fn generation(&self, id: u64) -> Option<u32> {
+        if id == 0 || id > MAX_ENTITY_ID {
+            return None;
+        }
+        self.generations.get((id - 1) as u32)
+    }
+
     /// Check if an entity exists
+    ///
+    /// Stale handles whose slot has since been reused are rejected.
     pub fn exists(&self, entity_id: EntityId) -> bool {
-        for i in 0..self.entities.len() {
-            let entity = self.entities.get(i).unwrap();
-            if entity.id() == entity_id {
-                return true;
-            }
-        }
-        false
+        self.generation(entity_id.id()) == Some(entity_id.generation())
+            && self.entities.contains_key(entity_id.id())
     }
 
-    /// Iterate over all entities
-    pub fn iter_entities(&self) -> EntityIterator {
+    /// Iterate over all entities, in ID order
+    pub fn iter_entities(&self) -> EntityIterator<'_> {
         EntityIterator {
-            entities: &self.entities,
+            entities: self.entities.values(),
             index: 0,
+            _marker: PhantomData,
         }
     }
 
-    /// Iterate over all entities mutably
-    pub fn iter_entities_mut(&mut self) -> EntityIteratorMut {
+    /// Iterate over all entities mutably, in ID order
+    pub fn iter_entities_mut(&mut self) -> EntityIteratorMut<'_> {
         EntityIteratorMut {
-            entities: &mut self.entities,
+            entities: self.entities.values(),
             index: 0,
+            _marker: PhantomData,
         }
     }
 }
 
-impl Default for EntityManager {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 // Soroban SDK trait implementations for EntityManager
 impl IntoVal<Env, Val> for EntityManager {
     fn into_val(&self, env: &Env) -> Val {
-        (self.next_id, self.entities.clone(), self.free_list.clone()).into_val(env)
+        (
+            self.next_id,
+            self.entities.clone(),
+            self.free_list.clone(),
+            self.generations.clone(),
+        )
+            .into_val(env)
     }
 }
 
+/// Saved form of an entity manager: next ID, entities, free list and
+/// generations
+type SavedEntities = (u64, Map<u64, Entity>, Vec<u64>, Vec<u32>);
+
 impl TryFromVal<Env, Val> for EntityManager {
     type Error = soroban_sdk::ConversionError;
 
     fn try_from_val(env: &Env, val: &Val) -> Result<Self, Self::Error> {
-        let (next_id, entities, free_list): (u64, Vec<Entity>, Vec<u64>) =
+        let (next_id, entities, free_list, generations): SavedEntities =
             TryFromVal::try_from_val(env, val)?;
         Ok(EntityManager {
             next_id,
             entities,
             free_list,
+            generations,
         })
     }
 }
 
+/// Mutable handle to an entity stored in an [`EntityManager`]
+///
+/// `soroban_sdk::Map` cannot hand out references into itself, so the handle
+/// holds a copy and writes it back under its ID on drop.
+pub struct EntityMut<'a> {
+    entities: &'a mut Map<u64, Entity>,
+    entity: Entity,
+}
+
+impl Deref for EntityMut<'_> {
+    type Target = Entity;
+
+    fn deref(&self) -> &Entity {
+        &self.entity
+    }
+}
+
+impl DerefMut for EntityMut<'_> {
+    fn deref_mut(&mut self) -> &mut Entity {
+        &mut self.entity
+    }
+}
+
+impl Drop for EntityMut<'_> {
+    fn drop(&mut self) {
+        self.entities
+            .set(self.entity.id().id(), self.entity.clone());
+    }
+}
+
 /// Iterator over entities
 pub struct EntityIterator<'a> {
-    entities: &'a Vec<Entity>,
+    entities: Vec<Entity>,
     index: u32,
+    _marker: PhantomData<&'a EntityManager>,
 }
 
 impl<'a> Iterator for EntityIterator<'a> {
@@ -294,8 +368,9 @@ impl<'a> Iterator for EntityIterator<'a> {
 
 /// Mutable iterator over entities
 pub struct EntityIteratorMut<'a> {
-    entities: &'a mut Vec<Entity>,
+    entities: Vec<Entity>,
     index: u32,
+    _marker: PhantomData<&'a mut EntityManager>,
 }
 
 impl<'a> Iterator for EntityIteratorMut<'a> {
@@ -329,14 +404,15 @@ mod tests {
     fn test_entity_creation() {
         let env = Env::default();
         let entity_id = EntityId::new(1, 0);
-        let entity = Entity::new(entity_id);
+        let entity = Entity::new(&env, entity_id);
         assert_eq!(entity.id(), entity_id);
         assert!(entity.is_empty());
     }
 
     #[test]
     fn test_entity_manager() {
-        let mut manager = EntityManager::new();
+        let env = Env::default();
+        let mut manager = EntityManager::new(&env);
         assert_eq!(manager.entity_count(), 0);
 
         let entity_id = manager.spawn();
@@ -347,4 +423,64 @@ mod tests {
         assert_eq!(manager.entity_count(), 0);
         assert!(!manager.exists(entity_id));
     }
+
+    #[test]
+    fn test_reused_id_bumps_generation() {
+        let env = Env::default();
+        let mut manager = EntityManager::new(&env);
+        let stale = manager.spawn();
+        assert!(manager.despawn(stale));
+
+        let reused = manager.spawn();
+        assert_eq!(reused.id(), stale.id());
+        assert_eq!(reused.generation(), stale.generation() + 1);
+        assert_eq!(manager.generation(reused.id()), Some(1));
+        assert!(manager.exists(reused));
+        assert!(!manager.exists(stale));
+        assert!(manager.get_entity(stale).is_none());
+        assert!(!manager.despawn(stale));
+    }
+
+    #[test]
+    fn test_entity_id_bits_roundtrip() {
+        let entity_id = EntityId::new(7, 3);
+        assert_eq!(entity_id.to_bits(), (3u64 << 32) | 7);
+        assert_eq!(EntityId::from_bits(entity_id.to_bits()), entity_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "entity id does not fit in 32 bits")]
+    fn test_entity_id_bits_reject_wide_ids() {
+        EntityId::new(MAX_ENTITY_ID + 1, 0).to_bits();
+    }
+
+    #[test]
+    fn test_out_of_range_ids_have_no_generation() {
+        let env = Env::default();
+        let mut manager = EntityManager::new(&env);
+        let entity_id = manager.spawn();
+
+        // Would alias the first slot if truncated to 32 bits
+        let forged = entity_id.id() + (1 << 32);
+        assert_eq!(manager.generation(entity_id.id()), Some(0));
+        assert_eq!(manager.generation(forged), None);
+        assert_eq!(manager.generation(0), None);
+        assert!(!manager.exists(EntityId::new(forged, 0)));
+    }
+
+    #[test]
+    fn test_get_entity_mut_writes_back() {
+        let env = Env::default();
+        let mut manager = EntityManager::new(&env);
+        let entity_id = manager.spawn();
+
+        manager
+            .get_entity_mut(entity_id)
+            .unwrap()
+            .add_component_type(Symbol::new(&env, "position"));
+
+        let entity = manager.get_entity(entity_id).unwrap();
+        assert!(entity.has_component(&Symbol::new(&env, "position")));
+        assert_eq!(entity.component_count(), 1);
+    }
 }
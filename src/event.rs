@@ -1,5 +1,8 @@
-use alloc::string::String;
-use soroban_sdk::{contracttype, symbol_short, Bytes, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use cougr_derive::Event;
+use soroban_sdk::{contracttype, Bytes, Env, IntoVal, Map, Symbol, Val, Vec};
 
 #[contracttype]
 #[derive(Debug, Clone)]
@@ -34,56 +37,231 @@ impl Event {
     }
 }
 
-pub struct EventReader<'a> {
-    events: &'a [Event],
-    event_type: Symbol,
-    read_index: usize,
+/// Double-buffered queue of the events of one type
+///
+/// Events live for two updates: [`EventQueue::update`] drops the previous
+/// buffer and moves the current one into its place, so every system gets a
+/// full tick to see an event regardless of run order. Each event has a
+/// sequence number that [`EventCursor`]s use to remember what they have read.
+/// Named readers keep their cursor in the queue itself, so it is saved with
+/// the world.
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct EventQueue {
+    /// Sequence number of the first event in `previous`
+    pub start: u64,
+    /// Events sent before the last update
+    pub previous: Vec<Event>,
+    /// Events sent since the last update
+    pub current: Vec<Event>,
+    /// Number of events already published as contract events
+    pub published: u64,
+    /// Number of events already read by each named reader
+    pub readers: Map<Symbol, u64>,
 }
-impl<'a> EventReader<'a> {
-    pub fn new(events: &'a [Event], event_type: Symbol) -> Self {
+impl EventQueue {
+    pub fn new(env: &Env) -> Self {
         Self {
-            events,
-            event_type,
-            read_index: 0,
+            start: 0,
+            previous: Vec::new(env),
+            current: Vec::new(env),
+            published: 0,
+            readers: Map::new(env),
         }
     }
-    pub fn read(&mut self) -> Option<&Event> {
-        while self.read_index < self.events.len() {
-            let event = &self.events[self.read_index];
-            self.read_index += 1;
-            if event.event_type() == &self.event_type {
-                return Some(event);
-            }
+    pub fn send(&mut self, event: Event) {
+        self.current.push_back(event);
+    }
+    /// Total number of events ever sent; the sequence number of the next event
+    pub fn event_count(&self) -> u64 {
+        self.start + self.previous.len() as u64 + self.current.len() as u64
+    }
+    /// Number of buffered events
+    pub fn len(&self) -> usize {
+        (self.previous.len() + self.current.len()) as usize
+    }
+    pub fn is_empty(&self) -> bool {
+        self.previous.is_empty() && self.current.is_empty()
+    }
+    /// Drop the previous buffer and start a new current one
+    pub fn update(&mut self) {
+        self.start += self.previous.len() as u64;
+        let env = self.current.env().clone();
+        self.previous = core::mem::replace(&mut self.current, Vec::new(&env));
+    }
+    /// Buffered events, oldest first
+    pub fn events(&self) -> Vec<Event> {
+        let mut events = self.previous.clone();
+        events.append(&self.current);
+        events
+    }
+    /// Buffered events not yet seen by `cursor`, advancing it past them
+    pub fn read<E>(&self, cursor: &mut EventCursor<E>) -> Vec<Event> {
+        let skip = cursor.last_event_count.saturating_sub(self.start);
+        let events = self.events();
+        cursor.last_event_count = self.event_count();
+        if skip >= events.len() as u64 {
+            return Vec::new(events.env());
         }
-        None
+        events.slice(skip as u32..)
     }
-    pub fn has_more(&self) -> bool {
-        self.read_index < self.events.len()
+    /// Cursor of the reader named `reader`, at the start if it never read
+    pub fn reader_cursor<E>(&self, reader: &Symbol) -> EventCursor<E> {
+        EventCursor::at(self.readers.get(reader.clone()).unwrap_or(0))
     }
-    pub fn reset(&mut self) {
-        self.read_index = 0;
+    /// Save the cursor of the reader named `reader`
+    pub fn set_reader_cursor<E>(&mut self, reader: &Symbol, cursor: EventCursor<E>) {
+        self.readers.set(reader.clone(), cursor.last_event_count);
     }
 }
 
-pub struct EventWriter<'a> {
-    events: &'a mut Vec<Event>,
+/// Typed view of an [`EventQueue`]
+pub struct Events<E: EventTrait> {
+    queue: EventQueue,
+    _marker: PhantomData<E>,
 }
-impl<'a> EventWriter<'a> {
-    pub fn new(events: &'a mut Vec<Event>) -> Self {
+impl<E: EventTrait> Events<E> {
+    pub fn new(env: &Env) -> Self {
+        Self::from_queue(EventQueue::new(env))
+    }
+    pub fn from_queue(queue: EventQueue) -> Self {
+        Self {
+            queue,
+            _marker: PhantomData,
+        }
+    }
+    pub fn into_queue(self) -> EventQueue {
+        self.queue
+    }
+    pub fn send(&mut self, event: E) {
+        let env = self.queue.current.env().clone();
+        let data = event.serialize(&env);
+        self.queue.send(Event::new(E::event_type(), data));
+    }
+    pub fn update(&mut self) {
+        self.queue.update();
+    }
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+    /// Buffered events, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = E> + '_ {
+        let env = self.queue.current.env();
+        self.queue
+            .events()
+            .into_iter()
+            .filter_map(move |event| E::deserialize(env, event.data()))
+    }
+    /// Buffered events not yet seen by `cursor`, advancing it past them
+    pub fn read(&self, cursor: &mut EventCursor<E>) -> alloc::vec::Vec<E> {
+        let env = self.queue.current.env();
+        self.queue
+            .read(cursor)
+            .into_iter()
+            .filter_map(|event| E::deserialize(env, event.data()))
+            .collect()
+    }
+}
+
+/// Read position of one reader in an [`EventQueue`]
+///
+/// A new cursor starts at the oldest buffered event. The position is a plain
+/// sequence number; readers that must survive across contract invocations
+/// keep it in the world under a name, see [`World::read_events_as`].
+///
+/// [`World::read_events_as`]: crate::world::World::read_events_as
+pub struct EventCursor<E> {
+    last_event_count: u64,
+    _marker: PhantomData<fn() -> E>,
+}
+impl<E> EventCursor<E> {
+    pub fn new() -> Self {
+        Self::at(0)
+    }
+    /// Cursor that has already seen the first `event_count` events
+    pub fn at(event_count: u64) -> Self {
+        Self {
+            last_event_count: event_count,
+            _marker: PhantomData,
+        }
+    }
+    pub fn last_event_count(&self) -> u64 {
+        self.last_event_count
+    }
+}
+impl<E> Default for EventCursor<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<E> Clone for EventCursor<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<E> Copy for EventCursor<E> {}
+
+/// Typed event reader for systems
+///
+/// Holds the events of type `E` the system has not seen yet, so each run sees
+/// each event once. A named system keeps its cursor in the world, where it is
+/// saved across contract invocations; an unnamed one keeps it in memory.
+pub struct EventReader<E: EventTrait> {
+    events: alloc::vec::Vec<E>,
+}
+impl<E: EventTrait> EventReader<E> {
+    pub fn new(events: alloc::vec::Vec<E>) -> Self {
         Self { events }
     }
-    pub fn send(&mut self, event: Event) {
-        self.events.push_back(event);
+    pub fn read(&self) -> impl Iterator<Item = &E> {
+        self.events.iter()
     }
-    pub fn send_with_data(&mut self, event_type: Symbol, data: Bytes) {
-        let event = Event::new(event_type, data);
-        self.send(event);
+    pub fn len(&self) -> usize {
+        self.events.len()
     }
-    pub fn send_batch(&mut self, events: Vec<Event>) {
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Typed event sender for systems
+///
+/// Events are encoded when sent and appended to the world's event queue once
+/// the system returns.
+pub struct EventWriter<E: EventTrait> {
+    env: Env,
+    pub(crate) events: Rc<RefCell<Vec<Event>>>,
+    _marker: PhantomData<E>,
+}
+impl<E: EventTrait> EventWriter<E> {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            env: env.clone(),
+            events: Rc::new(RefCell::new(Vec::new(env))),
+            _marker: PhantomData,
+        }
+    }
+    pub fn send(&mut self, event: E) {
+        let data = event.serialize(&self.env);
+        self.events
+            .borrow_mut()
+            .push_back(Event::new(E::event_type(), data));
+    }
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = E>) {
         for event in events {
             self.send(event);
         }
     }
+    /// Number of events sent and not yet applied to the world
+    pub fn len(&self) -> usize {
+        self.events.borrow().len() as usize
+    }
+    pub fn is_empty(&self) -> bool {
+        self.events.borrow().is_empty()
+    }
 }
 
 pub trait EventTrait {
@@ -95,11 +273,19 @@ pub trait EventTrait {
 }
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Event)]
+#[event(name = "collision")]
 pub struct CollisionEvent {
+    /// Packed [`EntityId`](crate::entity::EntityId) bits
     pub entity_a: u64,
+    /// Packed [`EntityId`](crate::entity::EntityId) bits
     pub entity_b: u64,
     pub collision_type: Symbol,
+    /// Contact normal pointing from `entity_a` to `entity_b`, in thousandths
+    pub normal_x: i32,
+    pub normal_y: i32,
+    /// Penetration depth, in thousandths of a unit
+    pub depth: i32,
 }
 impl CollisionEvent {
     pub fn new(entity_a: u64, entity_b: u64, collision_type: Symbol) -> Self {
@@ -107,75 +293,18 @@ impl CollisionEvent {
             entity_a,
             entity_b,
             collision_type,
+            normal_x: 0,
+            normal_y: 0,
+            depth: 0,
         }
     }
-    fn to_string(&self) -> String {
-        let s = "collision"; // Simplified for now
-        String::from(s)
-    }
-}
-impl EventTrait for CollisionEvent {
-    fn event_type() -> Symbol {
-        symbol_short!("collision")
-    }
-    fn serialize(&self, env: &Env) -> Bytes {
-        let mut bytes = Bytes::new(env);
-        bytes.append(&Bytes::from_slice(env, &self.entity_a.to_be_bytes()));
-        bytes.append(&Bytes::from_slice(env, &self.entity_b.to_be_bytes()));
-        // Serialize the collision_type symbol by converting to Val and then to u64
-        let symbol_val: Val = self.collision_type.to_val();
-        let symbol_bits = symbol_val.get_payload();
-        bytes.append(&Bytes::from_slice(env, &symbol_bits.to_be_bytes()));
-        bytes
-    }
-    fn deserialize(env: &Env, data: &Bytes) -> Option<Self> {
-        if data.len() < 24 {
-            return None;
-        }
-        let entity_a = u64::from_be_bytes([
-            data.get(0)?,
-            data.get(1)?,
-            data.get(2)?,
-            data.get(3)?,
-            data.get(4)?,
-            data.get(5)?,
-            data.get(6)?,
-            data.get(7)?,
-        ]);
-        let entity_b = u64::from_be_bytes([
-            data.get(8)?,
-            data.get(9)?,
-            data.get(10)?,
-            data.get(11)?,
-            data.get(12)?,
-            data.get(13)?,
-            data.get(14)?,
-            data.get(15)?,
-        ]);
-        // Deserialize the symbol from its Val representation
-        let symbol_bits = u64::from_be_bytes([
-            data.get(16)?,
-            data.get(17)?,
-            data.get(18)?,
-            data.get(19)?,
-            data.get(20)?,
-            data.get(21)?,
-            data.get(22)?,
-            data.get(23)?,
-        ]);
-        let symbol_val = Val::from_payload(symbol_bits);
-        let collision_type: Symbol = Symbol::try_from_val(env, &symbol_val).ok()?;
-        Some(Self {
-            entity_a,
-            entity_b,
-            collision_type,
-        })
-    }
 }
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Event)]
+#[event(name = "damage")]
 pub struct DamageEvent {
+    /// Packed [`EntityId`](crate::entity::EntityId) bits
     pub target_entity: u64,
     pub damage_amount: i32,
     pub damage_type: Symbol,
@@ -188,66 +317,115 @@ impl DamageEvent {
             damage_type,
         }
     }
-    fn to_string(&self) -> String {
-        let s = "damage"; // Simplified for now
-        String::from(s)
+}
+
+/// Sent by [`HealthSystem`](crate::system::HealthSystem) when an entity's
+/// health reaches zero, just before it is despawned
+#[contracttype]
+#[derive(Clone, Event)]
+#[event(name = "death")]
+pub struct DeathEvent {
+    /// Packed [`EntityId`](crate::entity::EntityId) bits
+    pub entity: u64,
+}
+impl DeathEvent {
+    pub fn new(entity: u64) -> Self {
+        Self { entity }
     }
 }
-impl EventTrait for DamageEvent {
-    fn event_type() -> Symbol {
-        symbol_short!("damage")
-    }
-    fn serialize(&self, env: &Env) -> Bytes {
-        let mut bytes = Bytes::new(env);
-        bytes.append(&Bytes::from_slice(env, &self.target_entity.to_be_bytes()));
-        bytes.append(&Bytes::from_slice(env, &self.damage_amount.to_be_bytes()));
-        // Serialize the damage_type symbol by converting to Val and then to u64
-        let symbol_val: Val = self.damage_type.to_val();
-        let symbol_bits = symbol_val.get_payload();
-        bytes.append(&Bytes::from_slice(env, &symbol_bits.to_be_bytes()));
-        bytes
-    }
-    fn deserialize(env: &Env, data: &Bytes) -> Option<Self> {
-        if data.len() < 20 {
-            return None;
+
+/// Event types that can be published as Soroban contract events
+///
+/// Mark a type with [`World::mark_published`](crate::world::World::mark_published)
+/// and call [`World::flush`](crate::world::World::flush) at the end of an
+/// invocation. Each pending event is published with the topics
+/// `(event_type, entity, ...)` and [`PublishEvent::publish_data`] as data.
+pub trait PublishEvent: EventTrait {
+    /// Packed [`EntityId`](crate::entity::EntityId)s added to the topics
+    ///
+    /// Soroban allows four topics, so at most three entities are used.
+    fn topic_entities(&self, env: &Env) -> Vec<u64> {
+        Vec::new(env)
+    }
+    /// Event data; defaults to the encoded event
+    fn publish_data(&self, env: &Env) -> Val {
+        self.serialize(env).into_val(env)
+    }
+    /// Topics for this event
+    fn topics(&self, env: &Env) -> Vec<Val> {
+        let mut topics: Vec<Val> = Vec::from_array(env, [Self::event_type().into_val(env)]);
+        for entity in self.topic_entities(env).iter().take(3) {
+            topics.push_back(entity.into_val(env));
         }
-        let target_entity = u64::from_be_bytes([
-            data.get(0)?,
-            data.get(1)?,
-            data.get(2)?,
-            data.get(3)?,
-            data.get(4)?,
-            data.get(5)?,
-            data.get(6)?,
-            data.get(7)?,
-        ]);
-        let damage_amount =
-            i32::from_be_bytes([data.get(8)?, data.get(9)?, data.get(10)?, data.get(11)?]);
-        // Deserialize the symbol from its Val representation
-        let symbol_bits = u64::from_be_bytes([
-            data.get(12)?,
-            data.get(13)?,
-            data.get(14)?,
-            data.get(15)?,
-            data.get(16)?,
-            data.get(17)?,
-            data.get(18)?,
-            data.get(19)?,
-        ]);
-        let symbol_val = Val::from_payload(symbol_bits);
-        let damage_type: Symbol = Symbol::try_from_val(env, &symbol_val).ok()?;
-        Some(Self {
-            target_entity,
-            damage_amount,
-            damage_type,
-        })
+        topics
+    }
+}
+
+impl PublishEvent for CollisionEvent {
+    fn topic_entities(&self, env: &Env) -> Vec<u64> {
+        Vec::from_array(env, [self.entity_a, self.entity_b])
+    }
+    fn publish_data(&self, env: &Env) -> Val {
+        self.into_val(env)
+    }
+}
+
+impl PublishEvent for DamageEvent {
+    fn topic_entities(&self, env: &Env) -> Vec<u64> {
+        Vec::from_array(env, [self.target_entity])
+    }
+    fn publish_data(&self, env: &Env) -> Val {
+        self.into_val(env)
+    }
+}
+
+impl PublishEvent for DeathEvent {
+    fn topic_entities(&self, env: &Env) -> Vec<u64> {
+        Vec::from_array(env, [self.entity])
+    }
+    fn publish_data(&self, env: &Env) -> Val {
+        self.into_val(env)
+    }
+}
+
+/// Topics and data of one contract event
+pub(crate) struct ContractEvent {
+    pub(crate) topics: Vec<Val>,
+    pub(crate) data: Val,
+}
+impl soroban_sdk::Event for ContractEvent {
+    fn topics(&self, _env: &Env) -> Vec<Val> {
+        self.topics.clone()
+    }
+    fn data(&self, _env: &Env) -> Val {
+        self.data
+    }
+}
+
+/// Publishes one type-erased event as a contract event
+pub(crate) type EventPublisher = fn(&Env, &Event) -> bool;
+
+/// Publish a type-erased event as a contract event, decoding it as `E`
+///
+/// Returns `false` if the event does not decode.
+pub(crate) fn publish_as<E: PublishEvent>(env: &Env, event: &Event) -> bool {
+    match E::deserialize(env, event.data()) {
+        Some(typed) => {
+            let contract_event = ContractEvent {
+                topics: PublishEvent::topics(&typed, env),
+                data: typed.publish_data(env),
+            };
+            env.events().publish_event(&contract_event);
+            true
+        }
+        None => false,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::Env;
+    use soroban_sdk::{symbol_short, Env};
 
     #[test]
     fn test_event_creation() {
@@ -288,10 +466,47 @@ mod tests {
         assert_eq!(damage_event.damage_type, deserialized.damage_type);
     }
 
-    // TODO: These tests require std vec! macro - need to adapt for Soroban
-    // #[test]
-    // fn test_event_reader() { ... }
+    #[test]
+    fn test_events_are_double_buffered() {
+        let env = Env::default();
+        let mut events = Events::<DamageEvent>::new(&env);
+        let mut cursor = EventCursor::new();
+        events.send(DamageEvent::new(1, 10, symbol_short!("fire")));
+
+        events.update();
+        events.send(DamageEvent::new(2, 20, symbol_short!("fire")));
+        assert_eq!(events.len(), 2);
+        let read = events.read(&mut cursor);
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].target_entity, 1);
+        assert!(events.read(&mut cursor).is_empty());
+
+        // The first event is dropped on its second update
+        events.update();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.iter().next().unwrap().target_entity, 2);
+
+        events.send(DamageEvent::new(3, 30, symbol_short!("fire")));
+        let read = events.read(&mut cursor);
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].target_entity, 3);
+        assert_eq!(cursor.last_event_count(), 3);
+    }
+
+    #[test]
+    fn test_stale_cursor_skips_dropped_events() {
+        let env = Env::default();
+        let mut events = Events::<DamageEvent>::new(&env);
+        let mut cursor = EventCursor::new();
+        for target in 0..3 {
+            events.send(DamageEvent::new(target, 1, symbol_short!("fire")));
+            events.update();
+            events.update();
+        }
+        events.send(DamageEvent::new(9, 1, symbol_short!("fire")));
 
-    // #[test]
-    // fn test_event_writer() { ... }
+        let read = events.read(&mut cursor);
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].target_entity, 9);
+    }
 }
@@ -0,0 +1,243 @@
+//! Component lifecycle hooks
+//!
+//! Hooks are plain functions registered per component type that the
+//! [`World`] runs synchronously whenever it adds, replaces or removes a
+//! component of that type. They keep derived state, such as a count of
+//! entities per team, in step with the components without every system doing
+//! the bookkeeping.
+//!
+//! - `on_add` runs when an entity gains a component it did not have.
+//! - `on_insert` runs on every insert, after `on_add`, including replacements.
+//! - `on_remove` runs when a component is removed, including by despawning.
+//!   The entity no longer lists the component, but its last value can still
+//!   be read with [`World::get`].
+//! - `on_despawn` runs for each component of an entity being despawned, just
+//!   before that component's `on_remove`.
+//!
+//! Hooks fire from [`World::add_component_to_entity`],
+//! [`World::remove_component_from_entity`] and [`World::despawn`], and so from
+//! everything built on them, including the write-back of changed values by
+//! [`World::query_mut`] and of raw data edited in place with
+//! [`World::modify`] or [`World::get_component_mut`].
+//!
+//! Hooks are not saved with the world: register them again after
+//! [`World::load`], as contracts do when they build their schedules.
+
+use crate::component::ComponentTrait;
+use crate::entity::EntityId;
+use crate::world::World;
+use soroban_sdk::Symbol;
+
+/// A function run on a component lifecycle event
+pub type ComponentHook = fn(&mut World, HookContext);
+
+/// What a [`ComponentHook`] was run for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookContext {
+    /// Entity whose component changed
+    pub entity: EntityId,
+    /// Type of the component that changed
+    pub component_type: Symbol,
+}
+
+/// Lifecycle hooks of one component type
+///
+/// Each event has at most one hook; setting it again replaces the previous
+/// one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ComponentHooks {
+    pub on_add: Option<ComponentHook>,
+    pub on_insert: Option<ComponentHook>,
+    pub on_remove: Option<ComponentHook>,
+    pub on_despawn: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Run `hook` when the component is added to an entity that lacked it
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_add = Some(hook);
+        self
+    }
+
+    /// Run `hook` whenever the component is inserted, new or replaced
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_insert = Some(hook);
+        self
+    }
+
+    /// Run `hook` when the component is removed from an entity
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_remove = Some(hook);
+        self
+    }
+
+    /// Run `hook` when an entity with the component is despawned
+    pub fn on_despawn(&mut self, hook: ComponentHook) -> &mut Self {
+        self.on_despawn = Some(hook);
+        self
+    }
+
+    /// Check if no hook is set
+    pub fn is_empty(&self) -> bool {
+        self.on_add.is_none()
+            && self.on_insert.is_none()
+            && self.on_remove.is_none()
+            && self.on_despawn.is_none()
+    }
+}
+
+impl World {
+    /// Get the lifecycle hooks of component type `T` for registration
+    pub fn register_component_hooks<T: ComponentTrait>(&mut self) -> &mut ComponentHooks {
+        self.components.hooks_mut(T::component_type())
+    }
+
+    /// Run `select`'s hook for the given component, if one is registered
+    pub(crate) fn run_hook(
+        &mut self,
+        select: fn(&ComponentHooks) -> Option<ComponentHook>,
+        entity: EntityId,
+        component_type: &Symbol,
+    ) {
+        let hook = self
+            .components
+            .hooks(component_type)
+            .and_then(|h| select(&h));
+        if let Some(hook) = hook {
+            hook(
+                self,
+                HookContext {
+                    entity,
+                    component_type: component_type.clone(),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Health, Team};
+    use cougr_derive::Resource;
+    use soroban_sdk::{contracttype, Env};
+
+    #[contracttype]
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+    struct TeamSize(u32);
+
+    #[contracttype]
+    #[derive(Clone, Debug, PartialEq, Eq, Resource)]
+    struct HookLog(soroban_sdk::Vec<Symbol>);
+
+    fn count_member(world: &mut World, _: HookContext) {
+        let size = world.resource::<TeamSize>().unwrap_or_default();
+        world.insert_resource(TeamSize(size.0 + 1));
+    }
+
+    fn drop_member(world: &mut World, context: HookContext) {
+        // The component is still readable while `on_remove` runs
+        assert!(world.get::<Team>(context.entity).is_some());
+        let size = world.resource::<TeamSize>().unwrap_or_default();
+        world.insert_resource(TeamSize(size.0 - 1));
+    }
+
+    fn log(world: &mut World, name: &str) {
+        let mut log = world
+            .resource::<HookLog>()
+            .unwrap_or_else(|| HookLog(soroban_sdk::Vec::new(world.env())));
+        log.0.push_back(Symbol::new(world.env(), name));
+        world.insert_resource(log);
+    }
+
+    fn team_size(world: &World) -> u32 {
+        world.resource::<TeamSize>().unwrap_or_default().0
+    }
+
+    fn logged(world: &World) -> alloc::vec::Vec<Symbol> {
+        let log = world.resource::<HookLog>();
+        log.map(|log| log.0.iter().collect()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_hooks_maintain_team_size() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world
+            .register_component_hooks::<Team>()
+            .on_add(count_member)
+            .on_remove(drop_member);
+
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.insert(a, Team(1));
+        world.insert(b, Team(1));
+        world.insert(b, Team(2));
+        world.insert(b, Health::new(10));
+        assert_eq!(team_size(&world), 2);
+
+        world.remove::<Team>(a);
+        world.remove::<Team>(a);
+        assert_eq!(team_size(&world), 1);
+
+        world.despawn(b);
+        assert_eq!(team_size(&world), 0);
+    }
+
+    #[test]
+    fn test_hook_order() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world
+            .register_component_hooks::<Team>()
+            .on_add(|world, _| log(world, "add"))
+            .on_insert(|world, _| log(world, "insert"))
+            .on_remove(|world, _| log(world, "remove"))
+            .on_despawn(|world, _| log(world, "despawn"));
+
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Team(1));
+        world.update::<Team, _>(entity, |team| team.0 = 2);
+        world.despawn(entity);
+
+        let expected = ["add", "insert", "insert", "despawn", "remove"];
+        let expected: alloc::vec::Vec<Symbol> = expected
+            .iter()
+            .map(|name| Symbol::new(&env, name))
+            .collect();
+        assert_eq!(logged(&world), expected);
+    }
+
+    #[test]
+    fn test_in_place_edits_run_on_insert() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world
+            .register_component_hooks::<Team>()
+            .on_insert(|world, _| log(world, "insert"));
+
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Team(1));
+        let team = Team::component_type();
+        assert!(world.modify(entity, &team, |_| {}));
+        drop(world.get_component_mut(entity, &team));
+        assert_eq!(logged(&world).len(), 3);
+    }
+
+    #[test]
+    fn test_hooks_can_change_the_world() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        // Losing a team despawns the entity, from inside the hook
+        world
+            .register_component_hooks::<Team>()
+            .on_remove(|world, context| {
+                world.despawn(context.entity);
+            });
+
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Team(1));
+        assert!(world.remove::<Team>(entity).is_some());
+        assert!(!world.exists(entity));
+    }
+}
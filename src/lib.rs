@@ -2,40 +2,71 @@
 #![allow(unsafe_code)]
 
 extern crate alloc;
+// Lets the derive macros refer to `::cougr_core` from inside this crate
+extern crate self as cougr_core;
 
-use soroban_sdk::{symbol_short, Bytes, Symbol, Vec};
+use soroban_sdk::{Symbol, Vec};
 
 // Global allocator for WASM
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 // Core ECS types adapted for Soroban
+pub mod change;
+pub mod codec;
+pub mod commands;
 pub mod component;
 pub mod components;
+pub mod condition;
 pub mod entity;
 pub mod event;
+pub mod hierarchy;
+pub mod hooks;
+pub mod math;
+pub mod observers;
+pub mod physics2d;
 pub mod query;
+pub mod relation;
 pub mod resource;
+pub mod scheduler;
 pub mod storage;
 pub mod system;
-pub mod systems;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 pub mod world;
 
 // Re-export core types
+pub use change::{Added, ChangeFilter, Changed, ComponentTicks};
+pub use codec::Codec;
+pub use commands::{Command, Commands};
 pub use component::{Component, ComponentId, ComponentStorage};
-pub use components::Position;
+pub use components::{
+    Collider, Health, Lifetime, Owner, Position, Rotation, Scale, Score, Team, Velocity,
+};
 pub use entity::{Entity, EntityId};
-pub use event::{Event, EventReader, EventWriter};
-pub use query::{Query, QueryState};
+pub use event::{Event, EventCursor, EventReader, EventWriter, Events, PublishEvent};
+pub use hierarchy::{ChildOf, Children};
+pub use hooks::{ComponentHook, ComponentHooks, HookContext};
+pub use math::{Fixed, Vec2, Vec3};
+pub use observers::{ObserverHandler, ObserverId, Trigger};
+pub use physics2d::{CircleCollider, PointCollider};
+pub use query::{Query, QueryData, QueryState};
+pub use relation::DespawnPolicy;
 pub use resource::Resource;
+pub use scheduler::{Schedule, Stage};
 pub use storage::{SparseStorage, Storage, TableStorage};
-pub use system::{IntoSystem, System, SystemParam};
-pub use systems::MovementSystem;
+pub use system::{
+    CollisionSystem, HealthSystem, IntoSystem, MovementSystem, QueryParam, Res, ResMut, System,
+    SystemParam,
+};
 pub use world::World;
 
+// Derive macros share names with the types above but live in the macro namespace
+pub use cougr_derive::{Codec, Component, Event, Resource};
+
 // Library functions for ECS operations
-pub fn create_world() -> World {
-    World::new()
+pub fn create_world(env: &soroban_sdk::Env) -> World {
+    World::new(env)
 }
 
 pub fn spawn_entity(world: &mut World, components: Vec<Component>) -> EntityId {
@@ -44,8 +75,7 @@ pub fn spawn_entity(world: &mut World, components: Vec<Component>) -> EntityId {
 }
 
 pub fn add_component(world: &mut World, entity_id: EntityId, component: Component) -> bool {
-    world.add_component_to_entity(entity_id, component);
-    true
+    world.add_component_to_entity(entity_id, component)
 }
 
 pub fn remove_component(world: &mut World, entity_id: EntityId, component_type: Symbol) -> bool {
@@ -60,47 +90,58 @@ pub fn get_component(
     world.get_component(entity_id, &component_type)
 }
 
-pub fn query_entities(
-    world: &World,
-    component_types: Vec<Symbol>,
-    env: &soroban_sdk::Env,
-) -> Vec<EntityId> {
-    // Since we can't easily convert Vec<Symbol> to &[Symbol] in Soroban,
-    // we'll need to restructure this. For now, return empty result.
-    Vec::new(env)
+pub fn query_entities(world: &World, component_types: Vec<Symbol>) -> Vec<EntityId> {
+    let component_types: alloc::vec::Vec<Symbol> = component_types.iter().collect();
+    world.query_entities(&component_types)
 }
 
 // Predule for common types
 pub mod prelude {
     pub use super::{
-        component::{Component, ComponentId, ComponentStorage},
+        change::{Added, ChangeFilter, Changed, ComponentTicks},
+        codec::Codec,
+        commands::{Command, Commands},
+        component::{Component, ComponentId, ComponentStorage, ComponentTrait},
+        components::{
+            Collider, Health, Lifetime, Owner, Position, Rotation, Scale, Score, Team, Velocity,
+        },
+        condition::Condition,
         entity::{Entity, EntityId},
-        event::{Event, EventReader, EventWriter},
-        query::{Query, QueryState},
+        event::{Event, EventCursor, EventReader, EventTrait, EventWriter, Events},
+        hierarchy::{ChildOf, Children},
+        hooks::{ComponentHook, ComponentHooks, HookContext},
+        math::{Fixed, Vec2, Vec3},
+        observers::{ObserverHandler, ObserverId, Trigger},
+        physics2d::{CircleCollider, PointCollider},
+        query::{Query, QueryData, QueryState},
+        relation::DespawnPolicy,
         resource::Resource,
+        scheduler::{Schedule, Stage},
         storage::{SparseStorage, Storage, TableStorage},
-        system::{IntoSystem, System, SystemParam},
+        system::{IntoSystem, QueryParam, Res, ResMut, System, SystemParam},
         world::World,
     };
+    pub use cougr_derive::{Codec, Component, Event, Resource};
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use soroban_sdk::Env;
 
     #[test]
     fn test_world_creation() {
-        let _env = Env::default();
-        let world = World::new();
+        let env = Env::default();
+        let world = create_world(&env);
         assert_eq!(world.entity_count(), 0);
     }
 
     #[test]
     fn test_entity_spawn() {
-        let _env = Env::default();
-        let mut world = World::new();
+        let env = Env::default();
+        let mut world = World::new(&env);
         let entity = world.spawn_empty();
         assert_eq!(world.entity_count(), 1);
+        assert!(world.exists(entity.id()));
     }
 }
@@ -0,0 +1,831 @@
+//! Deterministic fixed-point math for on-chain simulation
+//!
+//! Soroban contracts have no floating point, and simulation results must be
+//! identical on every validator. [`Fixed`] is a Q32.32 number stored in an
+//! `i64`; products and quotients go through `i128`, so nothing depends on the
+//! platform. The arithmetic operators saturate instead of panicking or
+//! wrapping, so overflow behaves the same in debug and release builds; the
+//! `checked_*` methods report it instead.
+//!
+//! Trigonometry uses lookup tables built at compile time with linear
+//! interpolation between entries. [`Trig`] takes the table resolution as a
+//! const parameter; [`Fixed::sin`], [`Fixed::cos`] and [`Fixed::atan2`] use
+//! [`TRIG`], with [`TRIG_STEPS`] entries per quarter turn.
+
+use crate::components::{Position, Velocity, UNIT};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use cougr_derive::Codec;
+
+const FRAC_BITS: u32 = 32;
+const ONE_BITS: i64 = 1 << FRAC_BITS;
+const FRAC_MASK: i64 = ONE_BITS - 1;
+const FRAC_PI_2_BITS: i64 = 6_746_518_852;
+
+/// Signed Q32.32 fixed-point number
+///
+/// The range is roughly ±2.1 billion with a resolution of about 2.3e-10.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Codec)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(ONE_BITS);
+    pub const HALF: Fixed = Fixed(ONE_BITS / 2);
+    pub const MIN: Fixed = Fixed(i64::MIN);
+    pub const MAX: Fixed = Fixed(i64::MAX);
+    /// Smallest positive value
+    pub const EPSILON: Fixed = Fixed(1);
+    pub const FRAC_PI_2: Fixed = Fixed(FRAC_PI_2_BITS);
+    // Multiples of FRAC_PI_2 so angle reduction splits into exact quadrants
+    pub const PI: Fixed = Fixed(FRAC_PI_2_BITS * 2);
+    pub const TAU: Fixed = Fixed(FRAC_PI_2_BITS * 4);
+
+    /// Create a value from its raw Q32.32 bits
+    pub const fn from_bits(bits: i64) -> Self {
+        Fixed(bits)
+    }
+
+    /// Get the raw Q32.32 bits
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    /// Create a value from a whole number
+    pub const fn from_int(value: i32) -> Self {
+        Fixed((value as i64) << FRAC_BITS)
+    }
+
+    /// Create `numerator / denominator`, saturating on overflow
+    ///
+    /// Panics if `denominator` is zero.
+    pub fn from_ratio(numerator: i64, denominator: i64) -> Self {
+        assert!(denominator != 0, "Fixed::from_ratio with zero denominator");
+        saturate(((numerator as i128) << FRAC_BITS) / denominator as i128)
+    }
+
+    /// Create a value from thousandths, the unit of the standard components
+    pub fn from_milli(value: i32) -> Self {
+        Fixed::from_ratio(value as i64, UNIT as i64)
+    }
+
+    /// Convert to thousandths, rounding to nearest and saturating
+    pub fn to_milli(self) -> i32 {
+        let milli = (self.0 as i128 * UNIT as i128 + (ONE_BITS as i128 / 2)) >> FRAC_BITS;
+        milli.clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+
+    /// Round down to a whole number
+    pub const fn to_int(self) -> i32 {
+        (self.0 >> FRAC_BITS) as i32
+    }
+
+    /// Round to the nearest whole number, halves away from zero
+    pub fn round(self) -> i32 {
+        let rounded = self.abs().saturating_add(Fixed::HALF).to_int();
+        if self.0 < 0 {
+            -rounded
+        } else {
+            rounded
+        }
+    }
+
+    /// Largest whole number not greater than `self`
+    pub const fn floor(self) -> Self {
+        Fixed(self.0 & !FRAC_MASK)
+    }
+
+    /// Smallest whole number not less than `self`, saturating
+    pub fn ceil(self) -> Self {
+        self.saturating_add(Fixed(FRAC_MASK)).floor()
+    }
+
+    /// Fractional part, always in `[0, 1)`
+    pub const fn fract(self) -> Self {
+        Fixed(self.0 & FRAC_MASK)
+    }
+
+    /// Absolute value, saturating at [`Fixed::MAX`]
+    pub const fn abs(self) -> Self {
+        Fixed(self.0.saturating_abs())
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(self, other: Fixed) -> Option<Fixed> {
+        self.0.checked_add(other.0).map(Fixed)
+    }
+
+    pub fn checked_sub(self, other: Fixed) -> Option<Fixed> {
+        self.0.checked_sub(other.0).map(Fixed)
+    }
+
+    pub fn checked_mul(self, other: Fixed) -> Option<Fixed> {
+        let product = (self.0 as i128 * other.0 as i128) >> FRAC_BITS;
+        i64::try_from(product).ok().map(Fixed)
+    }
+
+    /// Divide, returning `None` on overflow or division by zero
+    pub fn checked_div(self, other: Fixed) -> Option<Fixed> {
+        if other.0 == 0 {
+            return None;
+        }
+        let quotient = ((self.0 as i128) << FRAC_BITS) / other.0 as i128;
+        i64::try_from(quotient).ok().map(Fixed)
+    }
+
+    pub fn saturating_add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+
+    pub fn saturating_mul(self, other: Fixed) -> Fixed {
+        saturate((self.0 as i128 * other.0 as i128) >> FRAC_BITS)
+    }
+
+    /// Divide, saturating on overflow
+    ///
+    /// Division by zero saturates towards the sign of `self`, and `0 / 0` is
+    /// zero.
+    pub fn saturating_div(self, other: Fixed) -> Fixed {
+        if other.0 == 0 {
+            return match self.0 {
+                0 => Fixed::ZERO,
+                bits if bits > 0 => Fixed::MAX,
+                _ => Fixed::MIN,
+            };
+        }
+        saturate(((self.0 as i128) << FRAC_BITS) / other.0 as i128)
+    }
+
+    /// Square root, or `None` for negative values
+    pub fn checked_sqrt(self) -> Option<Fixed> {
+        if self.0 < 0 {
+            return None;
+        }
+        Some(Fixed(isqrt((self.0 as u128) << FRAC_BITS) as i64))
+    }
+
+    /// Square root, rounded down; negative values give zero
+    pub fn sqrt(self) -> Fixed {
+        self.checked_sqrt().unwrap_or(Fixed::ZERO)
+    }
+
+    /// Interpolate from `self` to `other`; `t = 0` gives `self` and `t = 1`
+    /// gives `other`
+    pub fn lerp(self, other: Fixed, t: Fixed) -> Fixed {
+        let delta = other.0 as i128 - self.0 as i128;
+        saturate(self.0 as i128 + ((delta * t.0 as i128) >> FRAC_BITS))
+    }
+
+    /// Sine of an angle in radians
+    pub fn sin(self) -> Fixed {
+        TRIG.sin(self)
+    }
+
+    /// Cosine of an angle in radians
+    pub fn cos(self) -> Fixed {
+        TRIG.cos(self)
+    }
+
+    /// Angle in radians, in `[-PI, PI]`, of the point `(x, self)`
+    pub fn atan2(self, x: Fixed) -> Fixed {
+        TRIG.atan2(self, x)
+    }
+}
+
+fn saturate(bits: i128) -> Fixed {
+    Fixed(bits.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+}
+
+/// Integer square root, rounded down
+pub const fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Start above the root and descend with Newton's method
+    let mut x = 1u128 << ((129 - n.leading_zeros()) / 2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+impl From<i32> for Fixed {
+    fn from(value: i32) -> Self {
+        Fixed::from_int(value)
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+    fn add(self, other: Fixed) -> Fixed {
+        self.saturating_add(other)
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, other: Fixed) -> Fixed {
+        self.saturating_sub(other)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, other: Fixed) -> Fixed {
+        self.saturating_mul(other)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+    fn div(self, other: Fixed) -> Fixed {
+        self.saturating_div(other)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, other: Fixed) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for Fixed {
+    fn mul_assign(&mut self, other: Fixed) {
+        *self = *self * other;
+    }
+}
+
+impl DivAssign for Fixed {
+    fn div_assign(&mut self, other: Fixed) {
+        *self = *self / other;
+    }
+}
+
+/// Table entries per quarter turn used by [`TRIG`]
+pub const TRIG_STEPS: usize = 256;
+
+/// Default trigonometry tables
+pub static TRIG: Trig<TRIG_STEPS> = Trig::new();
+
+/// Sine and arctangent lookup tables with `STEPS` entries per quarter turn
+///
+/// More steps trade contract size for accuracy; interpolation error shrinks
+/// with the square of the step count.
+pub struct Trig<const STEPS: usize> {
+    /// `sin(k / STEPS * PI / 2)`
+    sin: [i64; STEPS],
+    /// `atan(k / STEPS)`
+    atan: [i64; STEPS],
+}
+
+impl<const STEPS: usize> Trig<STEPS> {
+    /// Build the tables; evaluated at compile time when used in a `static`
+    pub const fn new() -> Self {
+        assert!(STEPS > 0, "Trig needs at least one step");
+        let mut sin = [0; STEPS];
+        let mut atan = [0; STEPS];
+        let mut k = 0;
+        while k < STEPS {
+            let t = ((k as i128) << FRAC_BITS) / STEPS as i128;
+            sin[k] = sin_series((t * FRAC_PI_2_BITS as i128) >> FRAC_BITS) as i64;
+            atan[k] = atan_unit(t) as i64;
+            k += 1;
+        }
+        Self { sin, atan }
+    }
+
+    /// Sine of an angle in radians
+    pub fn sin(&self, angle: Fixed) -> Fixed {
+        let angle = angle.0.rem_euclid(Fixed::TAU.0);
+        let quadrant = angle / FRAC_PI_2_BITS;
+        let offset = angle % FRAC_PI_2_BITS;
+        let t = ((offset as i128) << FRAC_BITS) / FRAC_PI_2_BITS as i128;
+        match quadrant {
+            0 => self.quarter_sin(t),
+            1 => self.quarter_sin(ONE_BITS as i128 - t),
+            2 => -self.quarter_sin(t),
+            _ => -self.quarter_sin(ONE_BITS as i128 - t),
+        }
+    }
+
+    /// Cosine of an angle in radians
+    pub fn cos(&self, angle: Fixed) -> Fixed {
+        let angle = angle.0.rem_euclid(Fixed::TAU.0);
+        self.sin(Fixed(angle + FRAC_PI_2_BITS))
+    }
+
+    /// Angle in radians, in `[-PI, PI]`, of the point `(x, y)`
+    ///
+    /// `atan2(0, 0)` is zero.
+    pub fn atan2(&self, y: Fixed, x: Fixed) -> Fixed {
+        let (ax, ay) = ((x.0 as i128).abs(), (y.0 as i128).abs());
+        if ax == 0 && ay == 0 {
+            return Fixed::ZERO;
+        }
+        let mut angle = if ay <= ax {
+            self.unit_atan((ay << FRAC_BITS) / ax)
+        } else {
+            FRAC_PI_2_BITS - self.unit_atan((ax << FRAC_BITS) / ay)
+        };
+        if x.0 < 0 {
+            angle = Fixed::PI.0 - angle;
+        }
+        if y.0 < 0 {
+            angle = -angle;
+        }
+        Fixed(angle)
+    }
+
+    /// `sin(t * PI / 2)` for `t` in `[0, 1]`
+    fn quarter_sin(&self, t: i128) -> Fixed {
+        Fixed(interpolate(&self.sin, ONE_BITS, t))
+    }
+
+    /// `atan(t)` for `t` in `[0, 1]`, as raw bits
+    fn unit_atan(&self, t: i128) -> i64 {
+        interpolate(&self.atan, FRAC_PI_2_BITS / 2, t)
+    }
+}
+
+impl<const STEPS: usize> Default for Trig<STEPS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Linearly interpolate `table` at `t` in `[0, 1]`, where `end` is the value
+/// at `t = 1`
+fn interpolate<const STEPS: usize>(table: &[i64; STEPS], end: i64, t: i128) -> i64 {
+    let position = t * STEPS as i128;
+    let index = (position >> FRAC_BITS) as usize;
+    if index >= STEPS {
+        return end;
+    }
+    let low = table[index] as i128;
+    let high = table.get(index + 1).map_or(end, |&value| value) as i128;
+    let fraction = position & FRAC_MASK as i128;
+    (low + (((high - low) * fraction) >> FRAC_BITS)) as i64
+}
+
+/// Taylor series for `sin(x)` with `x` in `[0, PI / 2]`, in Q32.32
+const fn sin_series(x: i128) -> i128 {
+    let x2 = (x * x) >> FRAC_BITS;
+    let mut term = x;
+    let mut sum = x;
+    let mut n = 1;
+    while n < 12 {
+        term = -((term * x2) >> FRAC_BITS) / ((2 * n) * (2 * n + 1));
+        sum += term;
+        n += 1;
+    }
+    sum
+}
+
+/// Taylor series for `atan(x)` with `x` in `[0, tan(PI / 8)]`, in Q32.32
+const fn atan_series(x: i128) -> i128 {
+    let x2 = (x * x) >> FRAC_BITS;
+    let mut power = x;
+    let mut sum = x;
+    let mut n = 1;
+    while n < 24 {
+        power = -((power * x2) >> FRAC_BITS);
+        sum += power / (2 * n + 1);
+        n += 1;
+    }
+    sum
+}
+
+/// `atan(x)` for `x` in `[0, 1]`, in Q32.32
+const fn atan_unit(x: i128) -> i128 {
+    // tan(PI / 8) = sqrt(2) - 1
+    const TAN_FRAC_PI_8: i128 = 1_779_033_704;
+    if x <= TAN_FRAC_PI_8 {
+        atan_series(x)
+    } else {
+        // atan(x) = PI / 4 - atan((1 - x) / (1 + x))
+        let one = ONE_BITS as i128;
+        let reduced = ((one - x) << FRAC_BITS) / (one + x);
+        FRAC_PI_2_BITS as i128 / 2 - atan_series(reduced)
+    }
+}
+
+/// Two-dimensional fixed-point vector
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Codec)]
+pub struct Vec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(Fixed::ZERO, Fixed::ZERO);
+
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self { x, y }
+    }
+
+    /// Unit vector pointing at `angle` radians
+    pub fn from_angle(angle: Fixed) -> Self {
+        Self::new(angle.cos(), angle.sin())
+    }
+
+    pub fn dot(self, other: Vec2) -> Fixed {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the 3D cross product; positive when `other` is
+    /// counter-clockwise from `self`
+    pub fn perp_dot(self, other: Vec2) -> Fixed {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> Fixed {
+        // Widen so long vectors do not saturate before the root is taken
+        let x = self.x.0 as i128;
+        let y = self.y.0 as i128;
+        let squared = (x * x) as u128 + (y * y) as u128;
+        Fixed(isqrt(squared).min(i64::MAX as u128) as i64)
+    }
+
+    pub fn distance(self, other: Vec2) -> Fixed {
+        (other - self).length()
+    }
+
+    /// Vector of length one in the same direction, or `None` for zero
+    pub fn normalize(self) -> Option<Vec2> {
+        let length = self.length();
+        if length == Fixed::ZERO {
+            return None;
+        }
+        Some(Vec2::new(self.x / length, self.y / length))
+    }
+
+    /// Angle of the vector in radians, in `[-PI, PI]`
+    pub fn angle(self) -> Fixed {
+        self.y.atan2(self.x)
+    }
+
+    pub fn lerp(self, other: Vec2, t: Fixed) -> Vec2 {
+        Vec2::new(self.x.lerp(other.x, t), self.y.lerp(other.y, t))
+    }
+
+    /// Clamp each component between `min` and `max`
+    pub fn clamp(self, min: Vec2, max: Vec2) -> Vec2 {
+        Vec2::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y))
+    }
+}
+
+impl From<Position> for Vec2 {
+    fn from(position: Position) -> Self {
+        Vec2::new(Fixed::from_milli(position.x), Fixed::from_milli(position.y))
+    }
+}
+
+impl From<Velocity> for Vec2 {
+    fn from(velocity: Velocity) -> Self {
+        Vec2::new(Fixed::from_milli(velocity.x), Fixed::from_milli(velocity.y))
+    }
+}
+
+impl From<Vec2> for Position {
+    fn from(vector: Vec2) -> Self {
+        Position::new(vector.x.to_milli(), vector.y.to_milli())
+    }
+}
+
+impl From<Vec2> for Velocity {
+    fn from(vector: Vec2) -> Self {
+        Velocity::new(vector.x.to_milli(), vector.y.to_milli())
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<Fixed> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, scale: Fixed) -> Vec2 {
+        Vec2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Div<Fixed> for Vec2 {
+    type Output = Vec2;
+    fn div(self, scale: Fixed) -> Vec2 {
+        Vec2::new(self.x / scale, self.y / scale)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+/// Three-dimensional fixed-point vector
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Codec)]
+pub struct Vec3 {
+    pub x: Fixed,
+    pub y: Fixed,
+    pub z: Fixed,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3::new(Fixed::ZERO, Fixed::ZERO, Fixed::ZERO);
+
+    pub const fn new(x: Fixed, y: Fixed, z: Fixed) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn dot(self, other: Vec3) -> Fixed {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> Fixed {
+        let x = self.x.0 as i128;
+        let y = self.y.0 as i128;
+        let z = self.z.0 as i128;
+        let squared = ((x * x) as u128 + (y * y) as u128).saturating_add((z * z) as u128);
+        Fixed(isqrt(squared).min(i64::MAX as u128) as i64)
+    }
+
+    pub fn distance(self, other: Vec3) -> Fixed {
+        (other - self).length()
+    }
+
+    /// Vector of length one in the same direction, or `None` for zero
+    pub fn normalize(self) -> Option<Vec3> {
+        let length = self.length();
+        if length == Fixed::ZERO {
+            return None;
+        }
+        Some(Vec3::new(self.x / length, self.y / length, self.z / length))
+    }
+
+    pub fn lerp(self, other: Vec3, t: Fixed) -> Vec3 {
+        Vec3::new(
+            self.x.lerp(other.x, t),
+            self.y.lerp(other.y, t),
+            self.z.lerp(other.z, t),
+        )
+    }
+
+    /// Clamp each component between `min` and `max`
+    pub fn clamp(self, min: Vec3, max: Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.clamp(min.x, max.x),
+            self.y.clamp(min.y, max.y),
+            self.z.clamp(min.z, max.z),
+        )
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<Fixed> for Vec3 {
+    type Output = Vec3;
+    fn mul(self, scale: Fixed) -> Vec3 {
+        Vec3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl Div<Fixed> for Vec3 {
+    type Output = Vec3;
+    fn div(self, scale: Fixed) -> Vec3 {
+        Vec3::new(self.x / scale, self.y / scale, self.z / scale)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Vec3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Vec3) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use proptest::prelude::*;
+
+    fn to_f64(value: Fixed) -> f64 {
+        value.to_bits() as f64 / ONE_BITS as f64
+    }
+
+    fn from_f64(value: f64) -> Fixed {
+        Fixed::from_bits((value * ONE_BITS as f64) as i64)
+    }
+
+    #[test]
+    fn test_constants_and_conversions() {
+        assert_eq!(Fixed::from_int(3).to_int(), 3);
+        assert_eq!(Fixed::from_int(-3).to_int(), -3);
+        assert_eq!(Fixed::from_ratio(7, 2).round(), 4);
+        assert_eq!(Fixed::from_ratio(-7, 2).round(), -4);
+        assert_eq!(Fixed::from_ratio(-7, 2).floor(), Fixed::from_int(-4));
+        assert_eq!(Fixed::from_ratio(-7, 2).ceil(), Fixed::from_int(-3));
+        assert_eq!(Fixed::from_milli(1500), Fixed::from_ratio(3, 2));
+        assert_eq!(Fixed::from_milli(-1234).to_milli(), -1234);
+        assert!((to_f64(Fixed::PI) - std::f64::consts::PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overflow_saturates() {
+        assert_eq!(Fixed::MAX + Fixed::ONE, Fixed::MAX);
+        assert_eq!(Fixed::MIN - Fixed::ONE, Fixed::MIN);
+        assert_eq!(Fixed::MAX * Fixed::from_int(2), Fixed::MAX);
+        assert_eq!(Fixed::ONE / Fixed::ZERO, Fixed::MAX);
+        assert_eq!(-Fixed::ONE / Fixed::ZERO, Fixed::MIN);
+        assert_eq!(-Fixed::MIN, Fixed::MAX);
+        assert_eq!(Fixed::MAX.checked_add(Fixed::EPSILON), None);
+        assert_eq!(Fixed::MAX.checked_mul(Fixed::from_int(2)), None);
+        assert_eq!(Fixed::ONE.checked_div(Fixed::ZERO), None);
+    }
+
+    #[test]
+    fn test_trig_reference_values() {
+        let quarter = Fixed::FRAC_PI_2;
+        assert_eq!(Fixed::ZERO.sin(), Fixed::ZERO);
+        assert_eq!(quarter.sin(), Fixed::ONE);
+        assert_eq!(Fixed::PI.sin(), Fixed::ZERO);
+        assert_eq!(Fixed::ZERO.cos(), Fixed::ONE);
+        assert_eq!(Fixed::PI.cos(), -Fixed::ONE);
+        assert_eq!(Fixed::ONE.atan2(Fixed::ZERO), quarter);
+        assert_eq!(Fixed::ZERO.atan2(-Fixed::ONE), Fixed::PI);
+        assert_eq!(Fixed::ZERO.atan2(Fixed::ZERO), Fixed::ZERO);
+    }
+
+    #[test]
+    fn test_coarse_tables_still_interpolate() {
+        let trig = Trig::<4>::new();
+        let angle = from_f64(0.3);
+        assert!((to_f64(trig.sin(angle)) - 0.3f64.sin()).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_vectors() {
+        let v = Vec2::new(Fixed::from_int(3), Fixed::from_int(4));
+        assert_eq!(v.length(), Fixed::from_int(5));
+        assert_eq!(v.dot(v), Fixed::from_int(25));
+        assert_eq!(Vec2::ZERO.normalize(), None);
+        assert_eq!(Vec2::new(Fixed::MAX, Fixed::MAX).length(), Fixed::MAX);
+        assert_eq!(
+            Vec3::new(Fixed::MIN, Fixed::MIN, Fixed::MIN).length(),
+            Fixed::MAX
+        );
+        assert_eq!(
+            Vec2::ZERO.lerp(v, Fixed::HALF),
+            Vec2::new(Fixed::from_ratio(3, 2), Fixed::from_int(2))
+        );
+        assert_eq!(
+            Position::from(Vec2::from(Position::new(1500, -250))),
+            Position::new(1500, -250)
+        );
+
+        let x = Vec3::new(Fixed::ONE, Fixed::ZERO, Fixed::ZERO);
+        let y = Vec3::new(Fixed::ZERO, Fixed::ONE, Fixed::ZERO);
+        assert_eq!(x.cross(y), Vec3::new(Fixed::ZERO, Fixed::ZERO, Fixed::ONE));
+        assert_eq!(x.distance(y), Fixed::from_int(2).sqrt());
+    }
+
+    #[test]
+    fn test_fixed_roundtrips_through_codec() {
+        let env = soroban_sdk::Env::default();
+        let value = Vec2::new(Fixed::PI, -Fixed::HALF);
+        let data = crate::codec::to_bytes(&env, &value);
+        assert_eq!(crate::codec::from_bytes::<Vec2>(&data), Some(value));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_arithmetic_matches_reference(a in -1.0e4f64..1.0e4, b in -1.0e4f64..1.0e4) {
+            let (fa, fb) = (from_f64(a), from_f64(b));
+            prop_assert!((to_f64(fa + fb) - (a + b)).abs() < 1e-6);
+            prop_assert!((to_f64(fa - fb) - (a - b)).abs() < 1e-6);
+            prop_assert!((to_f64(fa * fb) - a * b).abs() < 1e-4);
+            if b.abs() > 1e-3 {
+                prop_assert!((to_f64(fa / fb) - a / b).abs() < 1e-3);
+            }
+        }
+
+        #[test]
+        fn prop_sqrt_matches_reference(a in 0.0f64..2.0e9) {
+            let root = to_f64(from_f64(a).sqrt());
+            prop_assert!((root - a.sqrt()).abs() < 1e-4);
+        }
+
+        #[test]
+        fn prop_isqrt_is_floor(n in any::<u128>()) {
+            let root = isqrt(n);
+            prop_assert!(root * root <= n);
+            prop_assert!((root + 1).checked_mul(root + 1).map_or(true, |square| square > n));
+        }
+
+        #[test]
+        fn prop_sin_cos_match_reference(angle in -100.0f64..100.0) {
+            let fixed = from_f64(angle);
+            prop_assert!((to_f64(fixed.sin()) - angle.sin()).abs() < 1e-5);
+            prop_assert!((to_f64(fixed.cos()) - angle.cos()).abs() < 1e-5);
+        }
+
+        #[test]
+        fn prop_atan2_matches_reference(y in -1.0e3f64..1.0e3, x in -1.0e3f64..1.0e3) {
+            let angle = to_f64(from_f64(y).atan2(from_f64(x)));
+            prop_assert!((angle - y.atan2(x)).abs() < 1e-5);
+        }
+
+        #[test]
+        fn prop_lerp_stays_between_ends(a in -1.0e6f64..1.0e6, b in -1.0e6f64..1.0e6, t in 0.0f64..1.0) {
+            let value = from_f64(a).lerp(from_f64(b), from_f64(t));
+            let (low, high) = (from_f64(a.min(b)), from_f64(a.max(b)));
+            prop_assert!(value >= low && value <= high);
+        }
+    }
+}
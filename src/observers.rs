@@ -0,0 +1,361 @@
+//! Observers: handlers run immediately when an event is triggered
+//!
+//! Unlike events sent with [`World::send`], which wait in a queue until a
+//! system reads them, a triggered event runs its observers on the spot:
+//!
+//! - [`World::observe`] registers a global observer for an event type. It
+//!   runs for every entity the event reaches, or once if it has no targets.
+//! - [`World::observe_entity`] registers an observer for one entity. It runs
+//!   when that entity is a target, and is removed when the entity despawns.
+//!
+//! For each target, the global observers run first, then that entity's own,
+//! each in registration order. Observers added while a trigger runs wait for
+//! the next trigger.
+//!
+//! An event can bubble up the [hierarchy](crate::hierarchy): once the
+//! observers of a target have run, the event moves on to the target's parent
+//! while [`Trigger::propagate`] is set. [`World::trigger_propagating`] starts
+//! with it set, and any observer may change it.
+//!
+//! Observers are not saved with the world; register them again after
+//! [`World::load`].
+
+use crate::entity::EntityId;
+use crate::event::{Event, EventTrait};
+use crate::world::World;
+use soroban_sdk::Symbol;
+
+/// A function run when an observed event is triggered
+pub type ObserverHandler = fn(&mut World, &mut Trigger);
+
+/// Handle to a registered observer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObserverId(u32);
+
+impl ObserverId {
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+/// A registered observer
+#[derive(Clone, Debug)]
+pub(crate) struct Observer {
+    id: ObserverId,
+    event_type: Symbol,
+    /// Entity watched, or `None` for a global observer
+    entity: Option<EntityId>,
+    handler: ObserverHandler,
+}
+
+impl Observer {
+    pub(crate) fn is_global(&self) -> bool {
+        self.entity.is_none()
+    }
+}
+
+/// A triggered event, as seen by the observers it runs
+#[derive(Clone, Debug)]
+pub struct Trigger {
+    event: Event,
+    target: Option<EntityId>,
+    original_target: Option<EntityId>,
+    propagate: bool,
+}
+
+impl Trigger {
+    /// Get the raw event
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Decode the event as `E`
+    ///
+    /// Returns `None` if the event is of another type or does not decode.
+    pub fn decode<E: EventTrait>(&self) -> Option<E> {
+        if self.event.event_type() != &E::event_type() {
+            return None;
+        }
+        E::deserialize(self.event.data().env(), self.event.data())
+    }
+
+    /// Get the entity the event is currently at, if it has targets
+    pub fn target(&self) -> Option<EntityId> {
+        self.target
+    }
+
+    /// Get the entity the event was triggered for, before any propagation
+    pub fn original_target(&self) -> Option<EntityId> {
+        self.original_target
+    }
+
+    /// Set whether the event moves on to the target's parent
+    pub fn propagate(&mut self, propagate: bool) {
+        self.propagate = propagate;
+    }
+
+    /// Check if the event will move on to the target's parent
+    pub fn is_propagating(&self) -> bool {
+        self.propagate
+    }
+}
+
+impl World {
+    /// Run `handler` whenever an event of `event_type` is triggered
+    pub fn observe(&mut self, event_type: Symbol, handler: ObserverHandler) -> ObserverId {
+        self.add_observer(event_type, None, handler)
+    }
+
+    /// Run `handler` whenever an event of `event_type` targets `entity_id`
+    ///
+    /// Returns `None` if the entity does not exist.
+    pub fn observe_entity(
+        &mut self,
+        entity_id: EntityId,
+        event_type: Symbol,
+        handler: ObserverHandler,
+    ) -> Option<ObserverId> {
+        if !self.exists(entity_id) {
+            return None;
+        }
+        Some(self.add_observer(event_type, Some(entity_id), handler))
+    }
+
+    /// Remove an observer, returning whether it was registered
+    pub fn unobserve(&mut self, observer: ObserverId) -> bool {
+        match self.observers.iter().position(|o| o.id == observer) {
+            Some(index) => {
+                self.observers.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Trigger a typed event for `targets`, or for no entity if empty
+    pub fn trigger<E: EventTrait>(&mut self, event: E, targets: &[EntityId]) {
+        let data = event.serialize(self.env());
+        self.trigger_event(Event::new(E::event_type(), data), targets, false);
+    }
+
+    /// Trigger a typed event for `targets`, propagating it up the hierarchy
+    /// until an observer stops it
+    pub fn trigger_propagating<E: EventTrait>(&mut self, event: E, targets: &[EntityId]) {
+        let data = event.serialize(self.env());
+        self.trigger_event(Event::new(E::event_type(), data), targets, true);
+    }
+
+    /// Trigger a raw event for `targets`, or for no entity if empty
+    ///
+    /// With `propagate` set, each target's event moves on to its parent until
+    /// an observer calls [`Trigger::propagate`] with `false`.
+    pub fn trigger_event(&mut self, event: Event, targets: &[EntityId], propagate: bool) {
+        let newest = self.next_observer_id;
+        if targets.is_empty() {
+            let mut trigger = Trigger {
+                event,
+                target: None,
+                original_target: None,
+                propagate: false,
+            };
+            self.run_observers(&mut trigger, newest);
+            return;
+        }
+        for &target in targets {
+            let mut trigger = Trigger {
+                event: event.clone(),
+                target: Some(target),
+                original_target: Some(target),
+                propagate,
+            };
+            loop {
+                self.run_observers(&mut trigger, newest);
+                let parent = trigger.target.and_then(|target| self.parent(target));
+                match parent {
+                    Some(parent) if trigger.propagate => trigger.target = Some(parent),
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    fn add_observer(
+        &mut self,
+        event_type: Symbol,
+        entity: Option<EntityId>,
+        handler: ObserverHandler,
+    ) -> ObserverId {
+        let id = ObserverId(self.next_observer_id);
+        self.next_observer_id += 1;
+        self.observers.push(Observer {
+            id,
+            event_type,
+            entity,
+            handler,
+        });
+        id
+    }
+
+    /// Run the global, then the target's, observers registered before `newest`
+    fn run_observers(&mut self, trigger: &mut Trigger, newest: u32) {
+        self.run_matching(trigger, None, newest);
+        if let Some(target) = trigger.target {
+            self.run_matching(trigger, Some(target), newest);
+        }
+    }
+
+    fn run_matching(&mut self, trigger: &mut Trigger, entity: Option<EntityId>, newest: u32) {
+        // Walk by id rather than index, so observers may register or remove
+        // others without the list being cloned
+        let mut after = None;
+        while let Some((id, handler)) = self.next_observer(trigger, entity, after, newest) {
+            after = Some(id);
+            handler(self, trigger);
+        }
+    }
+
+    fn next_observer(
+        &self,
+        trigger: &Trigger,
+        entity: Option<EntityId>,
+        after: Option<ObserverId>,
+        newest: u32,
+    ) -> Option<(ObserverId, ObserverHandler)> {
+        self.observers
+            .iter()
+            .find(|o| {
+                Some(o.id) > after
+                    && o.id.0 < newest
+                    && o.entity == entity
+                    && &o.event_type == trigger.event.event_type()
+            })
+            .map(|o| (o.id, o.handler))
+    }
+
+    /// Remove the observers of a despawned entity
+    pub(crate) fn remove_entity_observers(&mut self, entity_id: EntityId) {
+        self.observers.retain(|o| o.entity != Some(entity_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Health;
+    use cougr_derive::{Event, Resource};
+    use soroban_sdk::{contracttype, symbol_short, Env};
+
+    #[contracttype]
+    #[derive(Clone, Debug, PartialEq, Eq, Event)]
+    struct Hit {
+        amount: i32,
+    }
+
+    #[contracttype]
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+    struct HitCount(u32);
+
+    fn count_hit(world: &mut World, _: &mut Trigger) {
+        let count = world.resource::<HitCount>().unwrap_or_default();
+        world.insert_resource(HitCount(count.0 + 1));
+    }
+
+    fn apply_hit(world: &mut World, trigger: &mut Trigger) {
+        let hit = trigger.decode::<Hit>().unwrap();
+        let target = trigger.target().unwrap();
+        world.update::<Health, _>(target, |health| health.damage(hit.amount));
+    }
+
+    fn hit_count(world: &World) -> u32 {
+        world.resource::<HitCount>().unwrap_or_default().0
+    }
+
+    #[test]
+    fn test_global_and_entity_observers() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.insert(a, Health::new(10));
+        world.insert(b, Health::new(10));
+        world.observe(Hit::event_type(), count_hit);
+        world.observe_entity(a, Hit::event_type(), apply_hit);
+
+        world.trigger(Hit { amount: 3 }, &[a, b]);
+        assert_eq!(hit_count(&world), 2);
+        assert_eq!(world.get::<Health>(a).unwrap().current, 7);
+        assert_eq!(world.get::<Health>(b).unwrap().current, 10);
+
+        world.trigger(Hit { amount: 3 }, &[]);
+        assert_eq!(hit_count(&world), 3);
+
+        // Other event types are ignored
+        world.trigger_event(
+            Event::new(symbol_short!("other"), soroban_sdk::Bytes::new(&env)),
+            &[a],
+            false,
+        );
+        assert_eq!(hit_count(&world), 3);
+    }
+
+    #[test]
+    fn test_unobserve_and_despawn_remove_observers() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let a = world.spawn_empty().id();
+        let global = world.observe(Hit::event_type(), count_hit);
+        world.observe_entity(a, Hit::event_type(), count_hit);
+
+        assert!(world.unobserve(global));
+        assert!(!world.unobserve(global));
+        world.trigger(Hit { amount: 1 }, &[a]);
+        assert_eq!(hit_count(&world), 1);
+
+        world.despawn(a);
+        let respawned = world.spawn_empty().id();
+        world.trigger(Hit { amount: 1 }, &[a, respawned]);
+        assert_eq!(hit_count(&world), 1);
+        assert_eq!(world.observe_entity(a, Hit::event_type(), count_hit), None);
+    }
+
+    #[test]
+    fn test_propagation_up_the_hierarchy() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let ship = world.spawn_empty().id();
+        let turret = world.spawn_empty().id();
+        let barrel = world.spawn_empty().id();
+        world.set_parent(turret, ship);
+        world.set_parent(barrel, turret);
+        world.observe_entity(ship, Hit::event_type(), count_hit);
+        world.observe_entity(turret, Hit::event_type(), count_hit);
+
+        world.trigger(Hit { amount: 1 }, &[barrel]);
+        assert_eq!(hit_count(&world), 0);
+
+        world.trigger_propagating(Hit { amount: 1 }, &[barrel]);
+        assert_eq!(hit_count(&world), 2);
+
+        // The turret absorbs hits before they reach the ship
+        world.observe_entity(turret, Hit::event_type(), |_, trigger| {
+            assert_ne!(trigger.target(), trigger.original_target());
+            trigger.propagate(false);
+        });
+        world.trigger_propagating(Hit { amount: 1 }, &[barrel]);
+        assert_eq!(hit_count(&world), 3);
+    }
+
+    #[test]
+    fn test_observers_added_during_trigger_wait() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world.observe(Hit::event_type(), |world, _| {
+            world.observe(Hit::event_type(), count_hit);
+        });
+
+        world.trigger(Hit { amount: 1 }, &[]);
+        assert_eq!(hit_count(&world), 0);
+        world.trigger(Hit { amount: 1 }, &[]);
+        assert_eq!(hit_count(&world), 1);
+    }
+}
@@ -0,0 +1,547 @@
+//! 2D collision detection
+//!
+//! Entities collide through one of three collider components placed at their
+//! [`Position`]: [`Collider`] (an axis-aligned box), [`CircleCollider`] and
+//! [`PointCollider`]. [`find_collisions`] first buckets colliders into a
+//! uniform [`Grid`] so only nearby pairs are tested, then runs the exact
+//! shape test on each candidate pair. [`CollisionSystem`] sends the results as
+//! [`CollisionEvent`]s.
+//!
+//! Like the standard components, all lengths are signed integers in
+//! thousandths of a unit. Shapes that only touch do not collide.
+
+use crate::components::{Collider, Position, UNIT};
+use crate::entity::EntityId;
+use crate::event::CollisionEvent;
+use crate::math::isqrt;
+use crate::system::System;
+use crate::world::World;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec as StdVec;
+use cougr_derive::Component;
+use soroban_sdk::{contracttype, symbol_short};
+
+/// Default [`Grid`] cell size used by [`CollisionSystem`]: 64 units
+pub const DEFAULT_CELL_SIZE: i32 = 64 * UNIT;
+
+/// Colliders spanning more cells than this are tested against every other
+/// collider instead of being inserted into each cell
+const MAX_CELLS_PER_COLLIDER: i64 = 16;
+
+/// Circle centred on an entity's [`Position`]
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Component)]
+#[component(name = "circle")]
+pub struct CircleCollider {
+    pub radius: i32,
+}
+impl CircleCollider {
+    pub fn new(radius: i32) -> Self {
+        Self { radius }
+    }
+}
+
+/// Collider occupying only an entity's [`Position`], such as a bullet
+///
+/// Points collide with boxes and circles that contain them, never with other
+/// points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Component)]
+#[component(name = "point")]
+pub struct PointCollider;
+
+/// Geometry of a collider, relative to its entity's position
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Aabb { half_width: i32, half_height: i32 },
+    Circle { radius: i32 },
+    Point,
+}
+
+impl Shape {
+    /// Corners `(min_x, min_y, max_x, max_y)` of the box bounding the shape at
+    /// `position`
+    pub fn bounds(&self, position: &Position) -> (i64, i64, i64, i64) {
+        let (half_width, half_height) = match *self {
+            Shape::Aabb {
+                half_width,
+                half_height,
+            } => (half_width as i64, half_height as i64),
+            Shape::Circle { radius } => (radius as i64, radius as i64),
+            Shape::Point => (0, 0),
+        };
+        let (x, y) = (position.x as i64, position.y as i64);
+        (
+            x - half_width,
+            y - half_height,
+            x + half_width,
+            y + half_height,
+        )
+    }
+}
+
+impl From<&Collider> for Shape {
+    fn from(collider: &Collider) -> Self {
+        Shape::Aabb {
+            half_width: collider.half_width,
+            half_height: collider.half_height,
+        }
+    }
+}
+
+impl From<&CircleCollider> for Shape {
+    fn from(collider: &CircleCollider) -> Self {
+        Shape::Circle {
+            radius: collider.radius,
+        }
+    }
+}
+
+/// How two overlapping shapes touch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contact {
+    /// Unit normal pointing from the first shape to the second, in thousandths
+    pub normal_x: i32,
+    pub normal_y: i32,
+    /// Distance to move the second shape along the normal to separate them,
+    /// in thousandths of a unit
+    pub depth: i32,
+}
+
+impl Contact {
+    fn new(normal_x: i64, normal_y: i64, depth: i64) -> Self {
+        Self {
+            normal_x: normal_x as i32,
+            normal_y: normal_y as i32,
+            depth: depth.clamp(0, i32::MAX as i64) as i32,
+        }
+    }
+
+    /// The same contact seen from the second shape
+    pub fn flipped(self) -> Self {
+        Self {
+            normal_x: -self.normal_x,
+            normal_y: -self.normal_y,
+            depth: self.depth,
+        }
+    }
+}
+
+/// Test whether shape `a` at `position_a` overlaps shape `b` at `position_b`
+pub fn intersect(
+    a: &Shape,
+    position_a: &Position,
+    b: &Shape,
+    position_b: &Position,
+) -> Option<Contact> {
+    match (*a, *b) {
+        (
+            Shape::Aabb {
+                half_width: wa,
+                half_height: ha,
+            },
+            Shape::Aabb {
+                half_width: wb,
+                half_height: hb,
+            },
+        ) => aabb_aabb(position_a, (wa, ha), position_b, (wb, hb)),
+        (
+            Shape::Aabb {
+                half_width,
+                half_height,
+            },
+            _,
+        ) => aabb_circle(position_a, (half_width, half_height), position_b, radius(b)),
+        (
+            _,
+            Shape::Aabb {
+                half_width,
+                half_height,
+            },
+        ) => aabb_circle(position_b, (half_width, half_height), position_a, radius(a))
+            .map(Contact::flipped),
+        _ => circle_circle(position_a, radius(a), position_b, radius(b)),
+    }
+}
+
+/// Radius of a round shape; points are circles of radius zero
+fn radius(shape: &Shape) -> i32 {
+    match *shape {
+        Shape::Circle { radius } => radius,
+        _ => 0,
+    }
+}
+
+/// `x² + y²`, widened so distances across the whole `i32` range fit
+fn squared_length(x: i64, y: i64) -> u128 {
+    (x as i128 * x as i128 + y as i128 * y as i128) as u128
+}
+
+fn sign(value: i64) -> i64 {
+    if value < 0 {
+        -1
+    } else {
+        1
+    }
+}
+
+fn aabb_aabb(
+    a: &Position,
+    half_a: (i32, i32),
+    b: &Position,
+    half_b: (i32, i32),
+) -> Option<Contact> {
+    let dx = b.x as i64 - a.x as i64;
+    let dy = b.y as i64 - a.y as i64;
+    let overlap_x = half_a.0 as i64 + half_b.0 as i64 - dx.abs();
+    let overlap_y = half_a.1 as i64 + half_b.1 as i64 - dy.abs();
+    if overlap_x <= 0 || overlap_y <= 0 {
+        return None;
+    }
+    // Separate along the axis of least overlap
+    if overlap_x <= overlap_y {
+        Some(Contact::new(sign(dx) * UNIT as i64, 0, overlap_x))
+    } else {
+        Some(Contact::new(0, sign(dy) * UNIT as i64, overlap_y))
+    }
+}
+
+fn circle_circle(a: &Position, radius_a: i32, b: &Position, radius_b: i32) -> Option<Contact> {
+    let dx = b.x as i64 - a.x as i64;
+    let dy = b.y as i64 - a.y as i64;
+    let reach = radius_a as i64 + radius_b as i64;
+    let distance_squared = squared_length(dx, dy);
+    if distance_squared >= squared_length(reach, 0) {
+        return None;
+    }
+    let distance = isqrt(distance_squared) as i64;
+    if distance == 0 {
+        return Some(Contact::new(UNIT as i64, 0, reach));
+    }
+    Some(Contact::new(
+        dx * UNIT as i64 / distance,
+        dy * UNIT as i64 / distance,
+        reach - distance,
+    ))
+}
+
+/// Box centred at `center` against a circle; the normal points from the box
+fn aabb_circle(
+    center: &Position,
+    half: (i32, i32),
+    circle: &Position,
+    radius: i32,
+) -> Option<Contact> {
+    let (half_width, half_height) = (half.0 as i64, half.1 as i64);
+    let radius = radius as i64;
+    let dx = circle.x as i64 - center.x as i64;
+    let dy = circle.y as i64 - center.y as i64;
+
+    if dx.abs() < half_width && dy.abs() < half_height {
+        // Circle centre inside the box: push out through the nearest edge
+        let to_edge_x = half_width - dx.abs();
+        let to_edge_y = half_height - dy.abs();
+        return if to_edge_x <= to_edge_y {
+            Some(Contact::new(sign(dx) * UNIT as i64, 0, to_edge_x + radius))
+        } else {
+            Some(Contact::new(0, sign(dy) * UNIT as i64, to_edge_y + radius))
+        };
+    }
+
+    let offset_x = dx - dx.clamp(-half_width, half_width);
+    let offset_y = dy - dy.clamp(-half_height, half_height);
+    let distance_squared = squared_length(offset_x, offset_y);
+    if distance_squared >= squared_length(radius, 0) {
+        return None;
+    }
+    let distance = isqrt(distance_squared) as i64;
+    if distance == 0 {
+        // Centre on the boundary
+        return if dx.abs() >= half_width {
+            Some(Contact::new(sign(dx) * UNIT as i64, 0, radius))
+        } else {
+            Some(Contact::new(0, sign(dy) * UNIT as i64, radius))
+        };
+    }
+    Some(Contact::new(
+        offset_x * UNIT as i64 / distance,
+        offset_y * UNIT as i64 / distance,
+        radius - distance,
+    ))
+}
+
+/// Uniform grid broad phase
+///
+/// Colliders are inserted into every cell their bounds cover; only colliders
+/// sharing a cell become candidate pairs. Pick a cell size around the size of
+/// a typical collider.
+pub struct Grid {
+    cell_size: i64,
+    cells: BTreeMap<(i64, i64), StdVec<usize>>,
+    /// Colliders too large to insert cell by cell
+    oversized: StdVec<usize>,
+    len: usize,
+}
+
+impl Grid {
+    /// Create an empty grid; panics if `cell_size` is not positive
+    pub fn new(cell_size: i32) -> Self {
+        assert!(cell_size > 0, "grid cell size must be positive");
+        Self {
+            cell_size: cell_size as i64,
+            cells: BTreeMap::new(),
+            oversized: StdVec::new(),
+            len: 0,
+        }
+    }
+
+    /// Insert collider `index` with bounds `(min_x, min_y, max_x, max_y)`
+    pub fn insert(&mut self, index: usize, bounds: (i64, i64, i64, i64)) {
+        let (min_x, min_y, max_x, max_y) = bounds;
+        let (first_x, first_y) = (self.cell(min_x), self.cell(min_y));
+        let (last_x, last_y) = (self.cell(max_x), self.cell(max_y));
+        self.len = self.len.max(index + 1);
+        // Saturate so colliders with extreme bounds count as oversized rather
+        // than overflowing
+        let span_x = last_x.saturating_sub(first_x).saturating_add(1);
+        let span_y = last_y.saturating_sub(first_y).saturating_add(1);
+        if span_x.saturating_mul(span_y) > MAX_CELLS_PER_COLLIDER {
+            self.oversized.push(index);
+            return;
+        }
+        for x in first_x..=last_x {
+            for y in first_y..=last_y {
+                self.cells.entry((x, y)).or_default().push(index);
+            }
+        }
+    }
+
+    fn cell(&self, coordinate: i64) -> i64 {
+        coordinate.div_euclid(self.cell_size)
+    }
+
+    /// Pairs `(i, j)` with `i < j` that may overlap, sorted and without
+    /// duplicates
+    pub fn candidate_pairs(&self) -> StdVec<(usize, usize)> {
+        let mut pairs = StdVec::new();
+        for members in self.cells.values() {
+            for (n, &i) in members.iter().enumerate() {
+                for &j in &members[n + 1..] {
+                    pairs.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+        for &i in &self.oversized {
+            for j in 0..self.len {
+                if i != j {
+                    pairs.push((i.min(j), i.max(j)));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+}
+
+/// A pair of colliding entities
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Collision {
+    pub entity_a: EntityId,
+    pub entity_b: EntityId,
+    /// Contact with the normal pointing from `entity_a` to `entity_b`
+    pub contact: Contact,
+}
+
+/// Find every pair of entities whose colliders overlap
+///
+/// Pairs are ordered by entity, with `entity_a` the lower of the two, so the
+/// result is deterministic. An entity with several collider components
+/// reports at most one collision per collider pair.
+pub fn find_collisions(world: &World, cell_size: i32) -> StdVec<Collision> {
+    let mut bodies: StdVec<(EntityId, Position, Shape)> = StdVec::new();
+    for (entity, position, collider) in world.query::<(Position, Collider)>() {
+        bodies.push((entity, position, Shape::from(&collider)));
+    }
+    for (entity, position, collider) in world.query::<(Position, CircleCollider)>() {
+        bodies.push((entity, position, Shape::from(&collider)));
+    }
+    for (entity, position, _) in world.query::<(Position, PointCollider)>() {
+        bodies.push((entity, position, Shape::Point));
+    }
+    bodies.sort_by_key(|(entity, _, _)| *entity);
+
+    let mut grid = Grid::new(cell_size);
+    for (index, (_, position, shape)) in bodies.iter().enumerate() {
+        grid.insert(index, shape.bounds(position));
+    }
+
+    let mut collisions = StdVec::new();
+    for (i, j) in grid.candidate_pairs() {
+        let (entity_a, position_a, shape_a) = &bodies[i];
+        let (entity_b, position_b, shape_b) = &bodies[j];
+        if entity_a == entity_b {
+            continue;
+        }
+        if let Some(contact) = intersect(shape_a, position_a, shape_b, position_b) {
+            collisions.push(Collision {
+                entity_a: *entity_a,
+                entity_b: *entity_b,
+                contact,
+            });
+        }
+    }
+    collisions
+}
+
+/// Collision detection system
+///
+/// Sends a [`CollisionEvent`] with the contact normal and depth for every
+/// pair of overlapping colliders found by [`find_collisions`].
+pub struct CollisionSystem {
+    cell_size: i32,
+}
+
+impl CollisionSystem {
+    /// Create a system using a broad-phase grid with `cell_size` cells
+    pub fn new(cell_size: i32) -> Self {
+        Self { cell_size }
+    }
+}
+
+impl From<&Collision> for CollisionEvent {
+    /// A `physical` collision event carrying the contact normal and depth
+    fn from(collision: &Collision) -> Self {
+        let mut event = CollisionEvent::new(
+            collision.entity_a.to_bits(),
+            collision.entity_b.to_bits(),
+            symbol_short!("physical"),
+        );
+        event.normal_x = collision.contact.normal_x;
+        event.normal_y = collision.contact.normal_y;
+        event.depth = collision.contact.depth;
+        event
+    }
+}
+
+impl Default for CollisionSystem {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl System for CollisionSystem {
+    type In = ();
+    type Out = ();
+
+    fn run(&mut self, world: &mut World, _input: Self::In) -> Self::Out {
+        for collision in find_collisions(world, self.cell_size) {
+            world.send(CollisionEvent::from(&collision));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    fn at(x: i32, y: i32) -> Position {
+        Position::new(x, y)
+    }
+
+    #[test]
+    fn test_narrow_phase_shapes() {
+        let square = Shape::Aabb {
+            half_width: 2,
+            half_height: 2,
+        };
+        let circle = Shape::Circle { radius: 2 };
+
+        let contact = intersect(&square, &at(0, 0), &square, &at(3, 1)).unwrap();
+        assert_eq!(contact, Contact::new(UNIT as i64, 0, 1));
+        assert_eq!(intersect(&square, &at(0, 0), &square, &at(4, 0)), None);
+
+        let contact = intersect(&circle, &at(0, 0), &circle, &at(0, -3)).unwrap();
+        assert_eq!(contact, Contact::new(0, -(UNIT as i64), 1));
+        assert_eq!(intersect(&circle, &at(0, 0), &circle, &at(3, 3)), None);
+
+        // Circle just past the corner of the box, but within reach diagonally
+        let contact = intersect(&square, &at(0, 0), &circle, &at(3, 3)).unwrap();
+        assert_eq!(contact.normal_x, contact.normal_y);
+        assert_eq!(
+            intersect(&circle, &at(3, 3), &square, &at(0, 0)),
+            Some(contact.flipped())
+        );
+        assert_eq!(intersect(&square, &at(0, 0), &circle, &at(4, 4)), None);
+
+        assert!(intersect(&Shape::Point, &at(1, 1), &square, &at(0, 0)).is_some());
+        assert!(intersect(&Shape::Point, &at(2, 0), &square, &at(0, 0)).is_none());
+        assert!(intersect(&Shape::Point, &at(1, 1), &circle, &at(0, 0)).is_some());
+        assert!(intersect(&Shape::Point, &at(1, 1), &Shape::Point, &at(1, 1)).is_none());
+
+        // Distances across the whole coordinate range do not overflow
+        let huge = Shape::Circle { radius: i32::MAX };
+        assert!(intersect(&huge, &at(-2_000_000_000, 0), &huge, &at(2_000_000_000, 0)).is_some());
+        assert!(intersect(
+            &circle,
+            &at(i32::MIN, i32::MIN),
+            &square,
+            &at(i32::MAX, i32::MAX)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_grid_only_pairs_neighbours() {
+        let mut grid = Grid::new(10);
+        grid.insert(0, (0, 0, 4, 4));
+        grid.insert(1, (8, 8, 12, 12));
+        grid.insert(2, (100, 100, 104, 104));
+        grid.insert(3, (-6, -6, -2, -2));
+        assert_eq!(grid.candidate_pairs(), [(0, 1)]);
+
+        // Large colliders are paired with everything
+        grid.insert(4, (-1000, -1000, 1000, 1000));
+        assert_eq!(
+            grid.candidate_pairs(),
+            [(0, 1), (0, 4), (1, 4), (2, 4), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn test_grid_extreme_bounds_are_oversized() {
+        let mut grid = Grid::new(1);
+        grid.insert(0, (0, 0, 0, 0));
+        grid.insert(1, (i64::MIN, i64::MIN, i64::MAX, i64::MAX));
+        grid.insert(2, (0, i64::MIN, 0, i64::MAX));
+        assert_eq!(grid.candidate_pairs(), [(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_collision_system_sends_contacts() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let ship = world.spawn_empty().id();
+        world.insert(ship, at(0, 0));
+        world.insert(ship, CircleCollider::new(20 * UNIT));
+        let asteroid = world.spawn_empty().id();
+        world.insert(asteroid, at(30 * UNIT, 0));
+        world.insert(asteroid, CircleCollider::new(28 * UNIT));
+        let bullet = world.spawn_empty().id();
+        world.insert(bullet, at(500 * UNIT, 0));
+        world.insert(bullet, PointCollider);
+        let wall = world.spawn_empty().id();
+        world.insert(wall, at(500 * UNIT, 0));
+        world.insert(wall, Collider::new(UNIT, 100 * UNIT));
+
+        CollisionSystem::default().run(&mut world, ());
+        let events: StdVec<CollisionEvent> = world.events::<CollisionEvent>().iter().collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].entity_a, ship.to_bits());
+        assert_eq!(events[0].entity_b, asteroid.to_bits());
+        assert_eq!((events[0].normal_x, events[0].normal_y), (UNIT, 0));
+        assert_eq!(events[0].depth, 18 * UNIT);
+        assert_eq!(events[1].entity_a, bullet.to_bits());
+        assert_eq!(events[1].entity_b, wall.to_bits());
+    }
+}
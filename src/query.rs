@@ -1,7 +1,94 @@
+use crate::change::ChangeFilter;
+use crate::component::{Component, ComponentTrait};
 use crate::entity::EntityId;
 use crate::world::World;
-use alloc::boxed::Box;
-use soroban_sdk::{Symbol, Vec};
+use alloc::vec::Vec as StdVec;
+use soroban_sdk::{Env, Symbol, Vec};
+
+/// Components fetched together by a typed query
+///
+/// Implemented for every [`ComponentTrait`] type and for tuples of up to four
+/// of them, so `world.query::<(Position, Velocity)>()` yields
+/// `(EntityId, Position, Velocity)` rows.
+pub trait QueryData: Sized {
+    /// Row yielded for each matching entity
+    type Item;
+
+    /// Component types an entity must have to match
+    fn component_types(env: &Env) -> Vec<Symbol>;
+
+    /// Decode the components of an entity
+    fn fetch(world: &World, entity_id: EntityId) -> Option<Self>;
+
+    /// Combine the decoded components with their entity into a row
+    fn into_item(self, entity_id: EntityId) -> Self::Item;
+
+    /// Write the components back, skipping any whose encoding is unchanged
+    fn write_back(self, world: &mut World, entity_id: EntityId);
+}
+
+impl<T: ComponentTrait> QueryData for T {
+    type Item = (EntityId, T);
+
+    fn component_types(env: &Env) -> Vec<Symbol> {
+        Vec::from_array(env, [T::component_type()])
+    }
+
+    fn fetch(world: &World, entity_id: EntityId) -> Option<Self> {
+        world.get::<T>(entity_id)
+    }
+
+    fn into_item(self, entity_id: EntityId) -> Self::Item {
+        (entity_id, self)
+    }
+
+    fn write_back(self, world: &mut World, entity_id: EntityId) {
+        let component_type = T::component_type();
+        let data = self.serialize(world.env());
+        if let Some(current) = world.get_component(entity_id, &component_type) {
+            if current.data() != &data {
+                let storage = current.storage();
+                world.add_component_to_entity(
+                    entity_id,
+                    Component::with_storage(component_type, data, storage),
+                );
+            }
+        }
+    }
+}
+
+macro_rules! impl_query_data_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: ComponentTrait),+> QueryData for ($($name,)+) {
+            type Item = (EntityId, $($name,)+);
+
+            fn component_types(env: &Env) -> Vec<Symbol> {
+                Vec::from_array(env, [$($name::component_type()),+])
+            }
+
+            fn fetch(world: &World, entity_id: EntityId) -> Option<Self> {
+                Some(($(world.get::<$name>(entity_id)?,)+))
+            }
+
+            #[allow(non_snake_case)]
+            fn into_item(self, entity_id: EntityId) -> Self::Item {
+                let ($($name,)+) = self;
+                (entity_id, $($name,)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn write_back(self, world: &mut World, entity_id: EntityId) {
+                let ($($name,)+) = self;
+                $(QueryData::write_back($name, world, entity_id);)+
+            }
+        }
+    };
+}
+
+impl_query_data_tuple!(A);
+impl_query_data_tuple!(A, B);
+impl_query_data_tuple!(A, B, C);
+impl_query_data_tuple!(A, B, C, D);
 
 /// A query for entities with specific components
 #[derive(Debug, Clone)]
@@ -14,11 +101,10 @@ pub struct Query {
 
 impl Query {
     /// Create a new query
-    pub fn new() -> Self {
-        let env = soroban_sdk::Env::default();
+    pub fn new(env: &Env) -> Self {
         Self {
-            required_components: Vec::new(&env),
-            excluded_components: Vec::new(&env),
+            required_components: Vec::new(env),
+            excluded_components: Vec::new(env),
         }
     }
 
@@ -36,8 +122,7 @@ impl Query {
 
     /// Execute the query on a world
     pub fn execute(&self, world: &World) -> Vec<EntityId> {
-        let env = soroban_sdk::Env::default();
-        let mut results = Vec::new(&env);
+        let mut results = Vec::new(world.env());
 
         for entity in world.iter_entities() {
             // Check if entity has all required components
@@ -60,19 +145,71 @@ impl Query {
         results
     }
 
+    /// Execute the query and decode the components in `Q` for each match
+    ///
+    /// Entities must also have every component in `Q`, in addition to this
+    /// query's own filters.
+    pub fn fetch<Q: QueryData>(&self, world: &World) -> StdVec<Q::Item> {
+        let query = self.requiring::<Q>(world.env());
+        query
+            .execute(world)
+            .iter()
+            .filter_map(|entity_id| Some(Q::fetch(world, entity_id)?.into_item(entity_id)))
+            .collect()
+    }
+
+    /// Execute the query and decode the components in `Q` for each match
+    /// that passes change filter `F` since `last_run`
+    pub fn fetch_filtered<Q: QueryData, F: ChangeFilter>(
+        &self,
+        world: &World,
+        last_run: u64,
+    ) -> StdVec<Q::Item> {
+        let query = self.requiring::<Q>(world.env());
+        query
+            .execute(world)
+            .iter()
+            .filter(|&entity_id| F::matches(world, entity_id, last_run))
+            .filter_map(|entity_id| Some(Q::fetch(world, entity_id)?.into_item(entity_id)))
+            .collect()
+    }
+
+    /// Execute the query and run `f` on the components in `Q` for each match
+    ///
+    /// Components modified by `f` are written back to the world.
+    pub fn fetch_mut<Q, F>(&self, world: &mut World, mut f: F)
+    where
+        Q: QueryData,
+        F: FnMut(EntityId, &mut Q),
+    {
+        let query = self.requiring::<Q>(world.env());
+        for entity_id in query.execute(world).iter() {
+            if let Some(mut data) = Q::fetch(world, entity_id) {
+                f(entity_id, &mut data);
+                data.write_back(world, entity_id);
+            }
+        }
+    }
+
     /// Check if the query is empty (no requirements)
     pub fn is_empty(&self) -> bool {
         self.required_components.is_empty() && self.excluded_components.is_empty()
     }
-}
 
-impl Default for Query {
-    fn default() -> Self {
-        Self::new()
+    /// Copy of this query that also requires every component in `Q`
+    pub(crate) fn requiring<Q: QueryData>(&self, env: &Env) -> Query {
+        let mut query = self.clone();
+        for component_type in Q::component_types(env) {
+            query = query.with_component(component_type);
+        }
+        query
     }
 }
 
 /// Query state for tracking query results
+///
+/// Remembers the change tick of its last execution, so it can tell when the
+/// results may be stale.
 #[derive(Debug, Clone)]
 pub struct QueryState {
     query: Query,
@@ -83,10 +220,10 @@ pub struct QueryState {
 impl QueryState {
     /// Create a new query state
     pub fn new(query: Query) -> Self {
-        let env = soroban_sdk::Env::default();
+        let last_results = Vec::new(query.required_components.env());
         Self {
             query,
-            last_results: Vec::new(&env),
+            last_results,
             last_execution_time: 0,
         }
     }
@@ -94,7 +231,7 @@ impl QueryState {
     /// Execute the query and update state
     pub fn execute(&mut self, world: &World) -> &Vec<EntityId> {
         self.last_results = self.query.execute(world);
-        self.last_execution_time = 0; // In a real implementation, this would be the current time
+        self.last_execution_time = world.change_tick();
         &self.last_results
     }
 
@@ -113,16 +250,27 @@ impl QueryState {
         self.last_results.len().try_into().unwrap()
     }
 
-    /// Get the last execution time
+    /// Get the change tick of the last execution, or 0 if never executed
     pub fn last_execution_time(&self) -> u64 {
         self.last_execution_time
     }
 
     /// Check if the query needs to be re-executed
-    pub fn needs_update(&self, current_time: u64) -> bool {
-        // In a real implementation, you might check if the world has changed
-        // For now, we'll just return true to always re-execute
-        true
+    ///
+    /// True if it never ran, or if a component type it filters on has been
+    /// added to or removed from an entity since. Queries without required
+    /// components match every entity, so they always need an update.
+    pub fn needs_update(&self, world: &World) -> bool {
+        if self.last_execution_time == 0 || self.query.required_components.is_empty() {
+            return true;
+        }
+        // Changes stamped with the execution tick may have come after it
+        let since = self.last_execution_time;
+        self.query
+            .required_components
+            .iter()
+            .chain(self.query.excluded_components.iter())
+            .any(|component_type| world.structure_tick(&component_type) >= since)
     }
 }
 
@@ -133,9 +281,9 @@ pub struct QueryBuilder {
 
 impl QueryBuilder {
     /// Create a new query builder
-    pub fn new() -> Self {
+    pub fn new(env: &Env) -> Self {
         Self {
-            query: Query::new(),
+            query: Query::new(env),
         }
     }
 
@@ -178,12 +326,6 @@ impl QueryBuilder {
     }
 }
 
-impl Default for QueryBuilder {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 /// Query filter for more complex querying
 pub trait QueryFilter {
     /// Check if an entity matches this filter
@@ -233,10 +375,9 @@ pub struct AllFilters {
 
 impl AllFilters {
     /// Create a new combined filter
-    pub fn new() -> Self {
-        let env = soroban_sdk::Env::default();
+    pub fn new(env: &Env) -> Self {
         Self {
-            filters: Vec::new(&env),
+            filters: Vec::new(env),
         }
     }
 
@@ -270,10 +411,9 @@ pub struct AnyFilter {
 
 impl AnyFilter {
     /// Create a new combined filter
-    pub fn new() -> Self {
-        let env = soroban_sdk::Env::default();
+    pub fn new(env: &Env) -> Self {
         Self {
-            filters: Vec::new(&env),
+            filters: Vec::new(env),
         }
     }
 
@@ -302,8 +442,7 @@ impl QueryFilter for AnyFilter {
 
 /// Execute a query with a custom filter
 pub fn query_with_filter(world: &World, filter: &dyn QueryFilter) -> Vec<EntityId> {
-    let env = soroban_sdk::Env::default();
-    let mut results = Vec::new(&env);
+    let mut results = Vec::new(world.env());
 
     for entity in world.iter_entities() {
         if filter.matches(world, entity.id()) {
@@ -317,17 +456,20 @@ pub fn query_with_filter(world: &World, filter: &dyn QueryFilter) -> Vec<EntityI
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::components::{Position, Velocity};
     use soroban_sdk::{symbol_short, Env};
 
     #[test]
     fn test_query_creation() {
-        let query = Query::new();
+        let env = Env::default();
+        let query = Query::new(&env);
         assert!(query.is_empty());
     }
 
     #[test]
     fn test_query_with_component() {
-        let query = Query::new()
+        let env = Env::default();
+        let query = Query::new(&env)
             .with_component(symbol_short!("position"))
             .with_component(symbol_short!("velocity"));
 
@@ -337,7 +479,8 @@ mod tests {
 
     #[test]
     fn test_query_builder() {
-        let query = QueryBuilder::new()
+        let env = Env::default();
+        let query = QueryBuilder::new(&env)
             .with_component(symbol_short!("position"))
             .without_component(symbol_short!("dead"))
             .build();
@@ -349,19 +492,47 @@ mod tests {
 
     #[test]
     fn test_query_state() {
-        let query = Query::new().with_component(symbol_short!("position"));
+        let env = Env::default();
+        let query = Query::new(&env).with_component(symbol_short!("position"));
         let mut query_state = QueryState::new(query);
 
-        let world = World::new();
+        let world = World::new(&env);
         let results = query_state.execute(&world);
         assert_eq!(results.len(), 0);
         assert!(query_state.is_empty());
     }
 
+    #[test]
+    fn test_query_state_needs_update() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let query = Query::new(&env)
+            .with_component(Position::component_type())
+            .without_component(Velocity::component_type());
+        let mut query_state = QueryState::new(query);
+        assert!(query_state.needs_update(&world));
+
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Position::new(0, 0));
+        world.increment_change_tick();
+        assert_eq!(query_state.execute(&world).len(), 1);
+        assert_eq!(query_state.last_execution_time(), 2);
+        assert!(!query_state.needs_update(&world));
+
+        // Changing values keeps the results valid; adding a filtered type does not
+        world.insert(entity, Position::new(1, 1));
+        assert!(!query_state.needs_update(&world));
+        world.insert(entity, Velocity::new(1, 1));
+        assert!(query_state.needs_update(&world));
+        world.increment_change_tick();
+        assert!(query_state.execute(&world).is_empty());
+    }
+
     #[test]
     fn test_with_component_filter() {
+        let env = Env::default();
         let filter = WithComponent::new(symbol_short!("position"));
-        let world = World::new();
+        let world = World::new(&env);
 
         // Since we have no entities with position components, this should return false
         let entity_id = EntityId::new(1, 0);
@@ -370,8 +541,9 @@ mod tests {
 
     #[test]
     fn test_without_component_filter() {
+        let env = Env::default();
         let filter = WithoutComponent::new(symbol_short!("position"));
-        let world = World::new();
+        let world = World::new(&env);
 
         // Since we have no entities with position components, this should return true
         let entity_id = EntityId::new(1, 0);
@@ -380,11 +552,12 @@ mod tests {
 
     #[test]
     fn test_all_filters() {
-        let filter = AllFilters::new()
+        let env = Env::default();
+        let filter = AllFilters::new(&env)
             .add_filter(symbol_short!("position"))
             .add_filter(symbol_short!("dead"));
 
-        let world = World::new();
+        let world = World::new(&env);
         let entity_id = EntityId::new(1, 0);
 
         // Should return false because no entity has position component
@@ -393,11 +566,12 @@ mod tests {
 
     #[test]
     fn test_any_filter() {
-        let filter = AnyFilter::new()
+        let env = Env::default();
+        let filter = AnyFilter::new(&env)
             .add_filter(symbol_short!("position"))
             .add_filter(symbol_short!("velocity"));
 
-        let world = World::new();
+        let world = World::new(&env);
         let entity_id = EntityId::new(1, 0);
 
         // Should return false because no entity has either component
@@ -406,10 +580,63 @@ mod tests {
 
     #[test]
     fn test_query_with_filter() {
+        let env = Env::default();
         let filter = WithComponent::new(symbol_short!("position"));
-        let world = World::new();
+        let world = World::new(&env);
 
         let results = query_with_filter(&world, &filter);
         assert_eq!(results.len(), 0);
     }
+
+    #[test]
+    fn test_typed_query_yields_components() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let moving = world.spawn_empty().id();
+        world.insert(moving, Position::new(1, 2));
+        world.insert(moving, Velocity::new(3, 4));
+        let still = world.spawn_empty().id();
+        world.insert(still, Position::new(5, 6));
+
+        let rows = world.query::<(Position, Velocity)>();
+        assert_eq!(
+            rows,
+            alloc::vec![(moving, Position::new(1, 2), Velocity::new(3, 4))]
+        );
+        assert_eq!(world.query::<Position>().len(), 2);
+    }
+
+    #[test]
+    fn test_typed_query_with_filters() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let moving = world.spawn_empty().id();
+        world.insert(moving, Position::new(1, 2));
+        world.insert(moving, Velocity::new(3, 4));
+        let still = world.spawn_empty().id();
+        world.insert(still, Position::new(5, 6));
+
+        let rows = QueryBuilder::new(&env)
+            .without_component(Velocity::component_type())
+            .build()
+            .fetch::<Position>(&world);
+        assert_eq!(rows, alloc::vec![(still, Position::new(5, 6))]);
+    }
+
+    #[test]
+    fn test_query_mut_writes_back() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Position::new(1, 2));
+        world.insert(entity, Velocity::new(3, 4));
+
+        world.query_mut::<(Position, Velocity), _>(|_, (position, velocity)| {
+            position.x += velocity.x;
+            position.y += velocity.y;
+        });
+
+        assert_eq!(world.get::<Position>(entity), Some(Position::new(4, 6)));
+        assert_eq!(world.get::<Velocity>(entity), Some(Velocity::new(3, 4)));
+    }
 }
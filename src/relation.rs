@@ -0,0 +1,318 @@
+//! Typed links between entities
+//!
+//! A relation kind is identified by a [`Symbol`] such as `targets` or
+//! `owned_by`, and links a source entity to any number of target entities.
+//! The world keeps a reverse index per kind, so both "what does X target?"
+//! ([`World::related`]) and "what targets X?" ([`World::related_to`]) are
+//! direct lookups.
+//!
+//! Despawning an entity removes every link to and from it. The relation's
+//! [`DespawnPolicy`] decides whether entities on the other side of the links
+//! are despawned too: sources with their target, targets with their source,
+//! or both.
+
+use crate::entity::EntityId;
+use crate::world::World;
+use alloc::vec::Vec as StdVec;
+use soroban_sdk::{contracttype, Env, Map, Symbol, Vec};
+
+/// What happens to the other side of a relation when one side is despawned
+#[contracttype]
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DespawnPolicy {
+    /// Only remove the links
+    #[default]
+    Unlink = 0,
+    /// Despawn the sources along with their target, such as items equipped
+    /// by a dead player
+    Cascade = 1,
+    /// Despawn the targets along with their source, such as the projectiles
+    /// fired by a destroyed turret
+    CascadeTargets = 2,
+    /// Despawn the entities on the other side whichever side is despawned
+    CascadeBoth = 3,
+}
+
+impl DespawnPolicy {
+    /// Check if sources are despawned along with their target
+    pub fn cascades_sources(&self) -> bool {
+        matches!(self, DespawnPolicy::Cascade | DespawnPolicy::CascadeBoth)
+    }
+
+    /// Check if targets are despawned along with their source
+    pub fn cascades_targets(&self) -> bool {
+        matches!(
+            self,
+            DespawnPolicy::CascadeTargets | DespawnPolicy::CascadeBoth
+        )
+    }
+}
+
+/// All links of one relation kind, as packed [`EntityId::to_bits`] values
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Relation {
+    pub policy: DespawnPolicy,
+    /// Targets of each source
+    pub targets: Map<u64, Vec<u64>>,
+    /// Sources linked to each target
+    pub sources: Map<u64, Vec<u64>>,
+}
+
+impl Relation {
+    pub fn new(env: &Env, policy: DespawnPolicy) -> Self {
+        Self {
+            policy,
+            targets: Map::new(env),
+            sources: Map::new(env),
+        }
+    }
+}
+
+/// Add `value` to the list under `key`, returning `false` if already present
+fn link(map: &mut Map<u64, Vec<u64>>, key: u64, value: u64) -> bool {
+    let mut values = map.get(key).unwrap_or_else(|| Vec::new(map.env()));
+    if values.contains(value) {
+        return false;
+    }
+    values.push_back(value);
+    map.set(key, values);
+    true
+}
+
+/// Remove `value` from the list under `key`, dropping the list once empty
+fn unlink(map: &mut Map<u64, Vec<u64>>, key: u64, value: u64) -> bool {
+    let Some(mut values) = map.get(key) else {
+        return false;
+    };
+    let Some(index) = values.first_index_of(value) else {
+        return false;
+    };
+    values.remove(index);
+    if values.is_empty() {
+        map.remove(key);
+    } else {
+        map.set(key, values);
+    }
+    true
+}
+
+fn to_ids(bits: Option<Vec<u64>>) -> StdVec<EntityId> {
+    bits.map(|bits| bits.iter().map(EntityId::from_bits).collect())
+        .unwrap_or_default()
+}
+
+impl World {
+    /// Declare relation `kind` with a despawn policy
+    ///
+    /// Kinds used without being registered get [`DespawnPolicy::Unlink`].
+    pub fn register_relation(&mut self, kind: Symbol, policy: DespawnPolicy) {
+        let relation = match self.relations.get(kind.clone()) {
+            Some(mut relation) => {
+                relation.policy = policy;
+                relation
+            }
+            None => Relation::new(self.env(), policy),
+        };
+        self.relations.set(kind, relation);
+    }
+
+    /// Link `source` to `target` by `kind`
+    ///
+    /// Returns `false` if either entity does not exist, they are the same
+    /// entity, or the link already exists.
+    pub fn relate(&mut self, source: EntityId, kind: &Symbol, target: EntityId) -> bool {
+        if source == target || !self.exists(source) || !self.exists(target) {
+            return false;
+        }
+        let mut relation = self
+            .relations
+            .get(kind.clone())
+            .unwrap_or_else(|| Relation::new(self.env(), DespawnPolicy::default()));
+        if !link(&mut relation.targets, source.to_bits(), target.to_bits()) {
+            return false;
+        }
+        link(&mut relation.sources, target.to_bits(), source.to_bits());
+        self.relations.set(kind.clone(), relation);
+        true
+    }
+
+    /// Remove the `kind` link from `source` to `target`, returning whether it
+    /// existed
+    pub fn unrelate(&mut self, source: EntityId, kind: &Symbol, target: EntityId) -> bool {
+        let Some(mut relation) = self.relations.get(kind.clone()) else {
+            return false;
+        };
+        if !unlink(&mut relation.targets, source.to_bits(), target.to_bits()) {
+            return false;
+        }
+        unlink(&mut relation.sources, target.to_bits(), source.to_bits());
+        self.relations.set(kind.clone(), relation);
+        true
+    }
+
+    /// Check if `source` is linked to `target` by `kind`
+    pub fn is_related(&self, source: EntityId, kind: &Symbol, target: EntityId) -> bool {
+        self.relations
+            .get(kind.clone())
+            .and_then(|relation| relation.targets.get(source.to_bits()))
+            .is_some_and(|targets| targets.contains(target.to_bits()))
+    }
+
+    /// Get the entities `source` is linked to by `kind`, in link order
+    pub fn related(&self, source: EntityId, kind: &Symbol) -> StdVec<EntityId> {
+        let relation = self.relations.get(kind.clone());
+        to_ids(relation.and_then(|relation| relation.targets.get(source.to_bits())))
+    }
+
+    /// Get the entities linked to `target` by `kind`, in link order
+    pub fn related_to(&self, target: EntityId, kind: &Symbol) -> StdVec<EntityId> {
+        let relation = self.relations.get(kind.clone());
+        to_ids(relation.and_then(|relation| relation.sources.get(target.to_bits())))
+    }
+
+    /// Remove every link to and from a despawned entity, returning the
+    /// entities that cascade-despawn with it
+    pub(crate) fn unlink_despawned(&mut self, entity_id: EntityId) -> StdVec<EntityId> {
+        let bits = entity_id.to_bits();
+        let mut cascaded = StdVec::new();
+        for (kind, mut relation) in self.relations.iter() {
+            let mut changed = false;
+            if let Some(targets) = relation.targets.get(bits) {
+                for target in targets.iter() {
+                    unlink(&mut relation.sources, target, bits);
+                    if relation.policy.cascades_targets() {
+                        cascaded.push(EntityId::from_bits(target));
+                    }
+                }
+                relation.targets.remove(bits);
+                changed = true;
+            }
+            if let Some(sources) = relation.sources.get(bits) {
+                for source in sources.iter() {
+                    unlink(&mut relation.targets, source, bits);
+                    if relation.policy.cascades_sources() {
+                        cascaded.push(EntityId::from_bits(source));
+                    }
+                }
+                relation.sources.remove(bits);
+                changed = true;
+            }
+            if changed {
+                self.relations.set(kind, relation);
+            }
+        }
+        cascaded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{contract, symbol_short};
+
+    #[contract]
+    pub struct TestContract;
+
+    #[test]
+    fn test_relate_and_query_both_directions() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let targets = symbol_short!("targets");
+        let turret_a = world.spawn_empty().id();
+        let turret_b = world.spawn_empty().id();
+        let ship = world.spawn_empty().id();
+        let station = world.spawn_empty().id();
+
+        assert!(world.relate(turret_a, &targets, ship));
+        assert!(world.relate(turret_a, &targets, station));
+        assert!(world.relate(turret_b, &targets, ship));
+        assert!(!world.relate(turret_b, &targets, ship));
+        assert!(!world.relate(ship, &targets, ship));
+
+        assert_eq!(world.related(turret_a, &targets), [ship, station]);
+        assert_eq!(world.related_to(ship, &targets), [turret_a, turret_b]);
+        assert!(world.is_related(turret_b, &targets, ship));
+        assert!(!world.is_related(ship, &targets, turret_b));
+        assert!(world.related(ship, &symbol_short!("owned_by")).is_empty());
+
+        assert!(world.unrelate(turret_a, &targets, ship));
+        assert!(!world.unrelate(turret_a, &targets, ship));
+        assert_eq!(world.related_to(ship, &targets), [turret_b]);
+    }
+
+    #[test]
+    fn test_despawn_unlinks_or_cascades() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let equipped = symbol_short!("equipped");
+        let targets = symbol_short!("targets");
+        world.register_relation(equipped.clone(), DespawnPolicy::Cascade);
+        let player = world.spawn_empty().id();
+        let sword = world.spawn_empty().id();
+        let gem = world.spawn_empty().id();
+        let enemy = world.spawn_empty().id();
+        world.relate(sword, &equipped, player);
+        world.relate(gem, &equipped, sword);
+        world.relate(enemy, &targets, player);
+        world.relate(player, &targets, enemy);
+
+        world.despawn(player);
+        assert!(!world.exists(sword));
+        assert!(!world.exists(gem));
+        assert!(world.exists(enemy));
+        assert!(world.related(enemy, &targets).is_empty());
+        assert!(world.related_to(enemy, &targets).is_empty());
+    }
+
+    #[test]
+    fn test_despawn_cascades_targets() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let fired = symbol_short!("fired");
+        let linked = symbol_short!("linked");
+        world.register_relation(fired.clone(), DespawnPolicy::CascadeTargets);
+        world.register_relation(linked.clone(), DespawnPolicy::CascadeBoth);
+        let turret = world.spawn_empty().id();
+        let shell = world.spawn_empty().id();
+        let fragment = world.spawn_empty().id();
+        let mount = world.spawn_empty().id();
+        world.relate(turret, &fired, shell);
+        world.relate(shell, &fired, fragment);
+        world.relate(mount, &linked, turret);
+
+        // Targets go with their source, but not sources with their target
+        world.despawn(fragment);
+        assert!(world.exists(shell));
+        world.despawn(turret);
+        assert!(!world.exists(shell));
+        assert!(!world.exists(mount));
+
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.relate(a, &linked, b);
+        world.despawn(a);
+        assert!(!world.exists(b));
+    }
+
+    #[test]
+    fn test_relations_are_saved_with_world() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let mut world = World::new(&env);
+            let owned_by = symbol_short!("owned_by");
+            let unit = world.spawn_empty().id();
+            let team = world.spawn_empty().id();
+            world.register_relation(owned_by.clone(), DespawnPolicy::Cascade);
+            world.relate(unit, &owned_by, team);
+            world.save(&symbol_short!("world"));
+
+            let mut loaded = World::load(&env, &symbol_short!("world")).unwrap();
+            assert_eq!(loaded.related_to(team, &owned_by), [unit]);
+            loaded.despawn(team);
+            assert!(!loaded.exists(unit));
+        });
+    }
+}
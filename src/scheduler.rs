@@ -0,0 +1,383 @@
+use crate::condition::{BoxedCondition, Condition};
+use crate::system::{IntoSystem, System};
+use crate::world::World;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec as StdVec;
+use soroban_sdk::Symbol;
+
+/// A type-erased system stored in a [`Schedule`]
+pub type BoxedSystem = Box<dyn System<In = (), Out = ()>>;
+
+/// Stage a scheduled system runs in
+///
+/// Stages run in declaration order; systems added without a stage run in
+/// [`Stage::Update`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    PreUpdate,
+    #[default]
+    Update,
+    PostUpdate,
+}
+
+impl Stage {
+    /// All stages in run order
+    pub const ALL: [Stage; 3] = [Stage::PreUpdate, Stage::Update, Stage::PostUpdate];
+}
+
+/// Errors found while ordering a schedule
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// A `before`/`after` constraint names a label no system in the same stage has
+    UnknownLabel(Symbol),
+    /// The `before`/`after` constraints of a stage form a cycle
+    Cycle(Stage),
+}
+
+/// A system registered in a schedule together with its ordering constraints
+struct ScheduledSystem {
+    system: BoxedSystem,
+    stage: Stage,
+    labels: StdVec<Symbol>,
+    before: StdVec<Symbol>,
+    after: StdVec<Symbol>,
+    conditions: StdVec<BoxedCondition>,
+}
+
+impl ScheduledSystem {
+    fn has_label(&self, label: &Symbol) -> bool {
+        self.labels.contains(label)
+    }
+}
+
+/// An ordered set of systems run together, typically once per contract tick
+///
+/// Ordering is deterministic: systems run stage by stage, and within a stage
+/// in the order they were added unless `before`/`after` constraints say
+/// otherwise. Constraints only relate systems in the same stage.
+///
+/// ```ignore
+/// let mut schedule = Schedule::new();
+/// schedule.add_system(physics_system).label(symbol_short!("physics"));
+/// schedule.add_system(collision_system).after(symbol_short!("physics"));
+/// schedule.add_system(scoring_system).in_stage(Stage::PostUpdate);
+/// schedule.run(&mut world);
+/// ```
+#[derive(Default)]
+pub struct Schedule {
+    systems: StdVec<ScheduledSystem>,
+    /// Cached run order, invalidated whenever a system is added or configured
+    order: Option<StdVec<usize>>,
+}
+
+impl Schedule {
+    /// Create an empty schedule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a system to the [`Stage::Update`] stage
+    ///
+    /// The returned handle configures the system's stage, labels and ordering.
+    pub fn add_system<S, Marker>(&mut self, system: S) -> SystemConfig<'_>
+    where
+        S: IntoSystem<(), (), Marker>,
+        S::System: 'static,
+    {
+        self.systems.push(ScheduledSystem {
+            system: Box::new(system.into_system()),
+            stage: Stage::default(),
+            labels: StdVec::new(),
+            before: StdVec::new(),
+            after: StdVec::new(),
+            conditions: StdVec::new(),
+        });
+        self.order = None;
+        let index = self.systems.len() - 1;
+        SystemConfig {
+            schedule: self,
+            index,
+        }
+    }
+
+    /// Get the number of systems in the schedule
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Check if the schedule has no systems
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// Order the systems, reporting unknown labels and cycles
+    ///
+    /// [`Schedule::run`] calls this on first use; calling it up front lets a
+    /// contract surface configuration mistakes without running anything.
+    pub fn initialize(&mut self) -> Result<(), ScheduleError> {
+        if self.order.is_none() {
+            let mut order = StdVec::with_capacity(self.systems.len());
+            for stage in Stage::ALL {
+                order.extend(self.sort_stage(stage)?);
+            }
+            self.order = Some(order);
+        }
+        Ok(())
+    }
+
+    /// Run every system whose conditions hold once, in order, then rotate the
+    /// world's event buffers and advance its tick and change tick
+    ///
+    /// Panics if the schedule cannot be ordered; see [`Schedule::initialize`].
+    pub fn run(&mut self, world: &mut World) {
+        if let Err(error) = self.initialize() {
+            panic!("invalid schedule: {:?}", error);
+        }
+        let order = self.order.as_ref().unwrap();
+        for &index in order {
+            let entry = &mut self.systems[index];
+            if entry
+                .conditions
+                .iter_mut()
+                .all(|condition| condition.evaluate(world))
+            {
+                entry.system.run(world, ());
+            }
+        }
+        world.update_events();
+        world.increment_tick();
+        world.increment_change_tick();
+    }
+
+    /// Topologically sort the systems of one stage, preferring insertion order
+    fn sort_stage(&self, stage: Stage) -> Result<StdVec<usize>, ScheduleError> {
+        let members: StdVec<usize> = (0..self.systems.len())
+            .filter(|&i| self.systems[i].stage == stage)
+            .collect();
+
+        // edges[a] lists the systems that must run after `a`
+        let mut edges: StdVec<StdVec<usize>> = vec![StdVec::new(); self.systems.len()];
+        let mut in_degree = vec![0usize; self.systems.len()];
+
+        for &i in &members {
+            let system = &self.systems[i];
+            for label in &system.before {
+                for j in self.labelled(&members, label)? {
+                    edges[i].push(j);
+                    in_degree[j] += 1;
+                }
+            }
+            for label in &system.after {
+                for j in self.labelled(&members, label)? {
+                    edges[j].push(i);
+                    in_degree[i] += 1;
+                }
+            }
+        }
+
+        let mut sorted = StdVec::with_capacity(members.len());
+        let mut done = vec![false; self.systems.len()];
+        while sorted.len() < members.len() {
+            let next = members
+                .iter()
+                .copied()
+                .find(|&i| !done[i] && in_degree[i] == 0)
+                .ok_or(ScheduleError::Cycle(stage))?;
+            done[next] = true;
+            for &j in &edges[next] {
+                in_degree[j] -= 1;
+            }
+            sorted.push(next);
+        }
+        Ok(sorted)
+    }
+
+    /// Systems in `members` carrying `label`
+    fn labelled(&self, members: &[usize], label: &Symbol) -> Result<StdVec<usize>, ScheduleError> {
+        let matches: StdVec<usize> = members
+            .iter()
+            .copied()
+            .filter(|&i| self.systems[i].has_label(label))
+            .collect();
+        if matches.is_empty() {
+            return Err(ScheduleError::UnknownLabel(label.clone()));
+        }
+        Ok(matches)
+    }
+}
+
+/// Handle for configuring a system just added to a [`Schedule`]
+pub struct SystemConfig<'s> {
+    schedule: &'s mut Schedule,
+    index: usize,
+}
+
+impl SystemConfig<'_> {
+    fn entry(&mut self) -> &mut ScheduledSystem {
+        self.schedule.order = None;
+        &mut self.schedule.systems[self.index]
+    }
+
+    /// Run the system in `stage`
+    pub fn in_stage(mut self, stage: Stage) -> Self {
+        self.entry().stage = stage;
+        self
+    }
+
+    /// Give the system a label other systems can order against
+    ///
+    /// Several systems may share a label; ordering against it then applies
+    /// to all of them.
+    pub fn label(mut self, label: Symbol) -> Self {
+        self.entry().labels.push(label);
+        self
+    }
+
+    /// Name the system, so the state it keeps between runs, such as event
+    /// cursors and the tick it last ran at, is saved with the world
+    ///
+    /// See [`System::set_name`]; names must be unique among the systems run
+    /// on a world.
+    pub fn name(mut self, name: Symbol) -> Self {
+        self.entry().system.set_name(name);
+        self
+    }
+
+    /// Run the system before every system labelled `label`
+    pub fn before(mut self, label: Symbol) -> Self {
+        self.entry().before.push(label);
+        self
+    }
+
+    /// Run the system after every system labelled `label`
+    pub fn after(mut self, label: Symbol) -> Self {
+        self.entry().after.push(label);
+        self
+    }
+
+    /// Only run the system on ticks where `condition` holds
+    ///
+    /// A system with several conditions runs only if all of them hold.
+    pub fn run_if<C: Condition + 'static>(mut self, condition: C) -> Self {
+        self.entry().conditions.push(Box::new(condition));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Position;
+    use crate::system::QueryParam;
+    use soroban_sdk::{symbol_short, Env};
+
+    /// Append `step` to the x coordinate trail of every position
+    fn record(world: &mut World, step: i32) {
+        world.query_mut::<Position, _>(|_, position| position.x = position.x * 10 + step);
+    }
+
+    fn trail(world: &World) -> i32 {
+        world.query::<Position>()[0].1.x
+    }
+
+    fn world_with_position(env: &Env) -> World {
+        let mut world = World::new(env);
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Position::new(0, 0));
+        world
+    }
+
+    #[test]
+    fn test_runs_in_insertion_order() {
+        let env = Env::default();
+        let mut world = world_with_position(&env);
+        let mut schedule = Schedule::new();
+        schedule.add_system(|world: &mut World, _: ()| record(world, 1));
+        schedule.add_system(|world: &mut World, _: ()| record(world, 2));
+        schedule.add_system(|mut query: QueryParam<Position>| {
+            for (_, position) in query.iter_mut() {
+                position.x = position.x * 10 + 3;
+            }
+        });
+
+        assert_eq!(schedule.len(), 3);
+        schedule.run(&mut world);
+        assert_eq!(trail(&world), 123);
+    }
+
+    #[test]
+    fn test_stages_and_ordering_constraints() {
+        let env = Env::default();
+        let mut world = world_with_position(&env);
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(|world: &mut World, _: ()| record(world, 4))
+            .in_stage(Stage::PostUpdate);
+        schedule
+            .add_system(|world: &mut World, _: ()| record(world, 3))
+            .after(symbol_short!("physics"));
+        schedule
+            .add_system(|world: &mut World, _: ()| record(world, 2))
+            .label(symbol_short!("physics"));
+        schedule
+            .add_system(|world: &mut World, _: ()| record(world, 1))
+            .in_stage(Stage::PreUpdate);
+
+        schedule.run(&mut world);
+        assert_eq!(trail(&world), 1234);
+    }
+
+    #[test]
+    fn test_invalid_schedules_are_reported() {
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(|_: &mut World, _: ()| {})
+            .after(symbol_short!("missing"));
+        assert_eq!(
+            schedule.initialize(),
+            Err(ScheduleError::UnknownLabel(symbol_short!("missing")))
+        );
+
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(|_: &mut World, _: ()| {})
+            .label(symbol_short!("a"))
+            .after(symbol_short!("b"));
+        schedule
+            .add_system(|_: &mut World, _: ()| {})
+            .label(symbol_short!("b"))
+            .after(symbol_short!("a"));
+        assert_eq!(
+            schedule.initialize(),
+            Err(ScheduleError::Cycle(Stage::Update))
+        );
+    }
+
+    #[test]
+    fn test_run_conditions() {
+        use crate::condition::{every_n_ticks, not, resource_exists};
+        use crate::resource::GameState;
+
+        let env = Env::default();
+        let mut world = world_with_position(&env);
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(|world: &mut World, _: ()| record(world, 1))
+            .run_if(every_n_ticks(2));
+        schedule
+            .add_system(|world: &mut World, _: ()| record(world, 2))
+            .run_if(not(resource_exists::<GameState>()));
+        schedule
+            .add_system(|world: &mut World, _: ()| world.init_resource::<GameState>())
+            .run_if(|world: &World| world.tick() == 1);
+
+        schedule.run(&mut world);
+        assert_eq!(trail(&world), 12);
+        schedule.run(&mut world);
+        assert_eq!(trail(&world), 122);
+        schedule.run(&mut world);
+        assert_eq!(trail(&world), 1221);
+        assert_eq!(world.tick(), 3);
+    }
+}
@@ -1,121 +1,475 @@
-use crate::component::Component;
+use crate::component::{Component, ComponentStorage};
 use crate::entity::EntityId;
-use soroban_sdk::{contracttype, Bytes, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, Bytes, Env, Map, Symbol, Vec};
 
+/// A dense column holding every instance of one component type
+///
+/// `entity_ids` and `data` are parallel arrays, so a system walking a column
+/// touches only the components it asked for. Entities are stored as
+/// [`EntityId::to_bits`] so each row identifies its entity's generation.
 #[contracttype]
 #[derive(Debug, Clone)]
-pub struct Storage {
+pub struct Column {
     pub entity_ids: Vec<u64>,
-    pub component_types: Vec<Symbol>,
-    pub component_data: Vec<Bytes>,
+    pub data: Vec<Bytes>,
+}
+
+impl Column {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            entity_ids: Vec::new(env),
+            data: Vec::new(env),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entity_ids.len().try_into().unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entity_ids.is_empty()
+    }
+}
+
+/// Column-per-component-type storage optimised for iteration
+///
+/// Removal swaps the last row into the freed slot so columns stay packed.
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct TableStorage {
+    /// One column per component type
+    pub columns: Map<Symbol, Column>,
+    /// Row of each `(entity bits, component type)` inside its column
+    pub rows: Map<(u64, Symbol), u32>,
+}
+
+impl TableStorage {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            columns: Map::new(env),
+            rows: Map::new(env),
+        }
+    }
+
+    /// Insert or replace a component
+    pub fn insert(&mut self, entity_id: EntityId, component_type: Symbol, data: Bytes) {
+        let key = (entity_id.to_bits(), component_type.clone());
+        let mut column = self
+            .columns
+            .get(component_type.clone())
+            .unwrap_or_else(|| Column::new(self.columns.env()));
+        match self.rows.get(key.clone()) {
+            Some(row) => column.data.set(row, data),
+            None => {
+                self.rows.set(key, column.entity_ids.len());
+                column.entity_ids.push_back(entity_id.to_bits());
+                column.data.push_back(data);
+            }
+        }
+        self.columns.set(component_type, column);
+    }
+
+    /// Remove a component, returning whether it was present
+    pub fn remove(&mut self, entity_id: EntityId, component_type: Symbol) -> bool {
+        let key = (entity_id.to_bits(), component_type.clone());
+        let row = match self.rows.get(key.clone()) {
+            Some(row) => row,
+            None => return false,
+        };
+        self.rows.remove(key);
+
+        let mut column = self.columns.get(component_type.clone()).unwrap();
+        let last = column.entity_ids.len() - 1;
+        if row != last {
+            let moved = column.entity_ids.get(last).unwrap();
+            column.entity_ids.set(row, moved);
+            column.data.set(row, column.data.get(last).unwrap());
+            self.rows.set((moved, component_type.clone()), row);
+        }
+        column.entity_ids.pop_back();
+        column.data.pop_back();
+
+        if column.is_empty() {
+            self.columns.remove(component_type);
+        } else {
+            self.columns.set(component_type, column);
+        }
+        true
+    }
+
+    /// Get a component's data
+    pub fn get(&self, entity_id: EntityId, component_type: Symbol) -> Option<Bytes> {
+        let row = self
+            .rows
+            .get((entity_id.to_bits(), component_type.clone()))?;
+        self.columns.get(component_type)?.data.get(row)
+    }
+
+    /// Check if a component is stored
+    pub fn contains(&self, entity_id: EntityId, component_type: Symbol) -> bool {
+        self.rows
+            .contains_key((entity_id.to_bits(), component_type))
+    }
+
+    /// Get the column for a component type
+    pub fn column(&self, component_type: Symbol) -> Option<Column> {
+        self.columns.get(component_type)
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len().try_into().unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// Map-backed storage optimised for rarely-present components such as tags
+///
+/// Inserting or removing never moves other entities' data.
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct SparseStorage {
+    /// Component data keyed by packed entity ID and component type
+    pub components: Map<(u64, Symbol), Bytes>,
+}
+
+impl SparseStorage {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            components: Map::new(env),
+        }
+    }
+
+    /// Insert or replace a component
+    pub fn insert(&mut self, entity_id: EntityId, component_type: Symbol, data: Bytes) {
+        self.components
+            .set((entity_id.to_bits(), component_type), data);
+    }
+
+    /// Remove a component, returning whether it was present
+    pub fn remove(&mut self, entity_id: EntityId, component_type: Symbol) -> bool {
+        self.components
+            .remove((entity_id.to_bits(), component_type))
+            .is_some()
+    }
+
+    /// Get a component's data
+    pub fn get(&self, entity_id: EntityId, component_type: Symbol) -> Option<Bytes> {
+        self.components.get((entity_id.to_bits(), component_type))
+    }
+
+    /// Check if a component is stored
+    pub fn contains(&self, entity_id: EntityId, component_type: Symbol) -> bool {
+        self.components
+            .contains_key((entity_id.to_bits(), component_type))
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len().try_into().unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+}
+
+/// Component storage routing each component type to a table or sparse backend
+///
+/// A component type is bound to the backend requested by the first
+/// [`Component`] of that type that is stored.
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct Storage {
+    /// Dense storage for components with [`ComponentStorage::Table`]
+    pub table: TableStorage,
+    /// Sparse storage for components with [`ComponentStorage::Sparse`]
+    pub sparse: SparseStorage,
+    /// Backend chosen for each component type
+    pub kinds: Map<Symbol, ComponentStorage>,
+    /// Component types stored for each packed entity ID
+    pub entity_index: Map<u64, Vec<Symbol>>,
 }
 
 impl Storage {
-    pub fn new() -> Self {
-        let env = soroban_sdk::Env::default();
+    pub fn new(env: &Env) -> Self {
         Self {
-            entity_ids: Vec::new(&env),
-            component_types: Vec::new(&env),
-            component_data: Vec::new(&env),
+            table: TableStorage::new(env),
+            sparse: SparseStorage::new(env),
+            kinds: Map::new(env),
+            entity_index: Map::new(env),
         }
     }
 
-    /// Add a component to storage
+    /// Get the backend a component type is stored in, if any are stored
+    pub fn storage_kind(&self, component_type: &Symbol) -> Option<ComponentStorage> {
+        self.kinds.get(component_type.clone())
+    }
+
+    /// Add a component to storage, replacing any existing one of the same type
     pub fn add_component(&mut self, entity_id: EntityId, component: Component) {
-        self.remove_component(entity_id, component.component_type().clone());
-        self.entity_ids.push_back(entity_id.id());
-        self.component_types
-            .push_back(component.component_type().clone());
-        self.component_data.push_back(component.data().clone());
+        let component_type = component.component_type().clone();
+        let kind = match self.storage_kind(&component_type) {
+            Some(kind) => kind,
+            None => {
+                self.kinds.set(component_type.clone(), component.storage());
+                component.storage()
+            }
+        };
+
+        if !self.has_component(entity_id, component_type.clone()) {
+            let mut types = self
+                .entity_index
+                .get(entity_id.to_bits())
+                .unwrap_or_else(|| Vec::new(self.kinds.env()));
+            types.push_back(component_type.clone());
+            self.entity_index.set(entity_id.to_bits(), types);
+        }
+
+        let data = component.data().clone();
+        match kind {
+            ComponentStorage::Table => self.table.insert(entity_id, component_type, data),
+            ComponentStorage::Sparse => self.sparse.insert(entity_id, component_type, data),
+        }
     }
 
     /// Remove a component from storage
     pub fn remove_component(&mut self, entity_id: EntityId, component_type: Symbol) -> bool {
-        let mut found = false;
-        let mut new_entity_ids = Vec::new(&soroban_sdk::Env::default());
-        let mut new_component_types = Vec::new(&soroban_sdk::Env::default());
-        let mut new_component_data = Vec::new(&soroban_sdk::Env::default());
-        for i in 0..self.entity_ids.len() {
-            let eid = self.entity_ids.get(i).unwrap();
-            let ctype = self.component_types.get(i).unwrap();
-            let cdata = self.component_data.get(i).unwrap();
-            if eid == entity_id.id() && ctype == component_type {
-                found = true;
+        let removed = match self.storage_kind(&component_type) {
+            Some(ComponentStorage::Table) => self.table.remove(entity_id, component_type.clone()),
+            Some(ComponentStorage::Sparse) => self.sparse.remove(entity_id, component_type.clone()),
+            None => false,
+        };
+        if !removed {
+            return false;
+        }
+        if let Some(mut types) = self.entity_index.get(entity_id.to_bits()) {
+            if let Some(index) = types.first_index_of(&component_type) {
+                types.remove(index);
+            }
+            if types.is_empty() {
+                self.entity_index.remove(entity_id.to_bits());
             } else {
-                new_entity_ids.push_back(eid);
-                new_component_types.push_back(ctype.clone());
-                new_component_data.push_back(cdata.clone());
+                self.entity_index.set(entity_id.to_bits(), types);
             }
         }
-        if found {
-            self.entity_ids = new_entity_ids;
-            self.component_types = new_component_types;
-            self.component_data = new_component_data;
-        }
-        found
+        true
     }
 
     /// Get a component from storage
     pub fn get_component(&self, entity_id: EntityId, component_type: Symbol) -> Option<Component> {
-        for i in 0..self.entity_ids.len() {
-            let eid = self.entity_ids.get(i).unwrap();
-            let ctype = self.component_types.get(i).unwrap();
-            let cdata = self.component_data.get(i).unwrap();
-            if eid == entity_id.id() && ctype == component_type {
-                return Some(Component::new(ctype.clone(), cdata.clone()));
-            }
-        }
-        None
+        let kind = self.storage_kind(&component_type)?;
+        let data = match kind {
+            ComponentStorage::Table => self.table.get(entity_id, component_type.clone()),
+            ComponentStorage::Sparse => self.sparse.get(entity_id, component_type.clone()),
+        }?;
+        Some(Component::with_storage(component_type, data, kind))
     }
 
     /// Check if a component exists in storage
     pub fn has_component(&self, entity_id: EntityId, component_type: Symbol) -> bool {
-        for i in 0..self.entity_ids.len() {
-            let eid = self.entity_ids.get(i).unwrap();
-            let ctype = self.component_types.get(i).unwrap();
-            if eid == entity_id.id() && ctype == component_type {
-                return true;
-            }
+        match self.storage_kind(&component_type) {
+            Some(ComponentStorage::Table) => self.table.contains(entity_id, component_type),
+            Some(ComponentStorage::Sparse) => self.sparse.contains(entity_id, component_type),
+            None => false,
         }
-        false
     }
 
     /// Get all components for an entity
     pub fn get_entity_components(&self, entity_id: EntityId) -> Vec<Component> {
-        let env = soroban_sdk::Env::default();
-        let mut components = Vec::new(&env);
-        for i in 0..self.entity_ids.len() {
-            let eid = self.entity_ids.get(i).unwrap();
-            let ctype = self.component_types.get(i).unwrap();
-            let cdata = self.component_data.get(i).unwrap();
-            if eid == entity_id.id() {
-                components.push_back(Component::new(ctype.clone(), cdata.clone()));
+        let mut components = Vec::new(self.kinds.env());
+        if let Some(types) = self.entity_index.get(entity_id.to_bits()) {
+            for ctype in types.iter() {
+                if let Some(component) = self.get_component(entity_id, ctype) {
+                    components.push_back(component);
+                }
             }
         }
         components
     }
 
+    /// Remove every component stored for an entity
+    pub fn remove_entity(&mut self, entity_id: EntityId) {
+        if let Some(types) = self.entity_index.get(entity_id.to_bits()) {
+            for ctype in types.iter() {
+                self.remove_component(entity_id, ctype);
+            }
+        }
+    }
+
+    /// Get the dense column of a table-stored component type for iteration
+    pub fn column(&self, component_type: &Symbol) -> Option<Column> {
+        match self.storage_kind(component_type)? {
+            ComponentStorage::Table => self.table.column(component_type.clone()),
+            ComponentStorage::Sparse => None,
+        }
+    }
+
     pub fn clear(&mut self) {
-        let env = soroban_sdk::Env::default();
-        self.entity_ids = Vec::new(&env);
-        self.component_types = Vec::new(&env);
-        self.component_data = Vec::new(&env);
+        *self = Self::new(&self.kinds.env().clone());
     }
 
     pub fn len(&self) -> usize {
-        self.entity_ids.len().try_into().unwrap()
+        self.table.len() + self.sparse.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.entity_ids.is_empty()
+        self.table.is_empty() && self.sparse.is_empty()
     }
 }
 
-impl Default for Storage {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{symbol_short, Env};
+
+    fn component(env: &Env, component_type: Symbol, value: u8) -> Component {
+        Component::new(component_type, Bytes::from_array(env, &[value]))
     }
-}
 
-pub type TableStorage = Storage;
-pub type SparseStorage = Storage;
+    fn tag(env: &Env, component_type: Symbol) -> Component {
+        Component::with_storage(component_type, Bytes::new(env), ComponentStorage::Sparse)
+    }
+
+    #[test]
+    fn test_add_get_remove() {
+        let env = Env::default();
+        let mut storage = Storage::new(&env);
+        let entity = EntityId::new(1, 0);
+
+        storage.add_component(entity, component(&env, symbol_short!("position"), 1));
+        storage.add_component(entity, component(&env, symbol_short!("velocity"), 2));
+        assert_eq!(storage.len(), 2);
+        assert!(storage.has_component(entity, symbol_short!("position")));
+
+        // Re-adding replaces the data instead of duplicating it
+        storage.add_component(entity, component(&env, symbol_short!("position"), 3));
+        assert_eq!(storage.len(), 2);
+        let position = storage
+            .get_component(entity, symbol_short!("position"))
+            .unwrap();
+        assert_eq!(position.data(), &Bytes::from_array(&env, &[3]));
+        assert_eq!(storage.get_entity_components(entity).len(), 2);
+
+        assert!(storage.remove_component(entity, symbol_short!("position")));
+        assert!(!storage.remove_component(entity, symbol_short!("position")));
+        assert!(!storage.has_component(entity, symbol_short!("position")));
+        assert_eq!(storage.get_entity_components(entity).len(), 1);
+    }
+
+    #[test]
+    fn test_remove_entity() {
+        let env = Env::default();
+        let mut storage = Storage::new(&env);
+        let a = EntityId::new(1, 0);
+        let b = EntityId::new(2, 0);
+
+        storage.add_component(a, component(&env, symbol_short!("position"), 1));
+        storage.add_component(a, tag(&env, symbol_short!("frozen")));
+        storage.add_component(b, component(&env, symbol_short!("position"), 3));
+
+        storage.remove_entity(a);
+        assert_eq!(storage.len(), 1);
+        assert!(storage.get_entity_components(a).is_empty());
+        assert!(storage.has_component(b, symbol_short!("position")));
+    }
+
+    #[test]
+    fn test_routing_by_storage_kind() {
+        let env = Env::default();
+        let mut storage = Storage::new(&env);
+        let entity = EntityId::new(1, 0);
+
+        storage.add_component(entity, component(&env, symbol_short!("position"), 1));
+        storage.add_component(entity, tag(&env, symbol_short!("frozen")));
+
+        assert_eq!(storage.table.len(), 1);
+        assert_eq!(storage.sparse.len(), 1);
+        assert_eq!(
+            storage
+                .get_component(entity, symbol_short!("frozen"))
+                .unwrap()
+                .storage(),
+            ComponentStorage::Sparse
+        );
+        assert_eq!(
+            storage
+                .get_component(entity, symbol_short!("position"))
+                .unwrap()
+                .storage(),
+            ComponentStorage::Table
+        );
+        assert!(storage.column(&symbol_short!("position")).is_some());
+        assert!(storage.column(&symbol_short!("frozen")).is_none());
+    }
+
+    #[test]
+    fn test_table_swap_remove_keeps_rows_consistent() {
+        let env = Env::default();
+        let mut table = TableStorage::new(&env);
+        let ctype = symbol_short!("position");
+        for id in 1..=3 {
+            table.insert(
+                EntityId::new(id, 0),
+                ctype.clone(),
+                Bytes::from_array(&env, &[id as u8]),
+            );
+        }
+
+        // Removing the first row moves the last entity into its slot
+        assert!(table.remove(EntityId::new(1, 0), ctype.clone()));
+        let column = table.column(ctype.clone()).unwrap();
+        assert_eq!(column.len(), 2);
+        assert_eq!(
+            column.entity_ids.get(0),
+            Some(EntityId::new(3, 0).to_bits())
+        );
+        assert_eq!(
+            table.get(EntityId::new(3, 0), ctype.clone()),
+            Some(Bytes::from_array(&env, &[3]))
+        );
+        assert_eq!(
+            table.get(EntityId::new(2, 0), ctype.clone()),
+            Some(Bytes::from_array(&env, &[2]))
+        );
+
+        assert!(table.remove(EntityId::new(2, 0), ctype.clone()));
+        assert!(table.remove(EntityId::new(3, 0), ctype.clone()));
+        assert!(table.is_empty());
+        assert!(table.column(ctype).is_none());
+    }
+
+    /// CPU instructions spent looking up one component in a storage holding
+    /// `entities` entities with two components each
+    fn lookup_cost(entities: u64) -> u64 {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let mut storage = Storage::new(&env);
+        for id in 1..=entities {
+            let entity = EntityId::new(id, 0);
+            storage.add_component(entity, component(&env, symbol_short!("position"), 1));
+            storage.add_component(entity, component(&env, symbol_short!("velocity"), 2));
+        }
+
+        let target = EntityId::new(entities / 2 + 1, 0);
+        let budget = env.cost_estimate().budget();
+        let before = budget.cpu_instruction_cost();
+        assert!(storage
+            .get_component(target, symbol_short!("velocity"))
+            .is_some());
+        budget.cpu_instruction_cost() - before
+    }
+
+    #[test]
+    fn bench_lookup_cost_scaling() {
+        let cost_10 = lookup_cost(10);
+        let cost_100 = lookup_cost(100);
+        let cost_1000 = lookup_cost(1000);
+
+        // A linear scan grows 100x from 10 to 1000 entities; a keyed lookup
+        // should stay within a small constant factor.
+        assert!(cost_100 < cost_10 * 4, "10: {cost_10}, 100: {cost_100}");
+        assert!(cost_1000 < cost_10 * 8, "10: {cost_10}, 1000: {cost_1000}");
+    }
+}
@@ -1,7 +1,17 @@
-use crate::component::Component;
-use crate::entity::{Entity, EntityId};
-use crate::event::{DamageEvent, EventTrait};
+use crate::change::{ChangeFilter, ComponentTicks};
+use crate::components::{Health, Position, Velocity};
+use crate::entity::EntityId;
+use crate::event::{
+    DamageEvent, DeathEvent, Event, EventCursor, EventReader, EventTrait, EventWriter,
+};
+use crate::query::QueryData;
+use crate::resource::ResourceTrait;
 use crate::world::World;
+use alloc::rc::Rc;
+use alloc::vec::Vec as StdVec;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
 use soroban_sdk::{symbol_short, Symbol, Vec};
 
 /// A system in the ECS world
@@ -13,103 +23,53 @@ pub trait System {
 
     /// Run the system
     fn run(&mut self, world: &mut World, input: Self::In) -> Self::Out;
-}
-
-/// A system parameter that can be used in systems
-pub trait SystemParam {
-    /// The type of the parameter
-    type Param;
-    /// The type of the fetched data
-    type Fetch;
-
-    /// Fetch the parameter from the world
-    fn fetch(world: &World) -> Self::Fetch;
 
-    /// Fetch the parameter mutably from the world
-    fn fetch_mut(world: &mut World) -> Self::Fetch;
+    /// Name the system, so state it keeps between runs is saved in the world
+    ///
+    /// Contracts rebuild their systems on every invocation; a named system
+    /// picks up where the previous invocation left off. Names must be unique
+    /// among the systems run on a world. Systems without state ignore this.
+    fn set_name(&mut self, _name: Symbol) {}
 }
 
-/// A query for entities with specific components
-pub struct Query {
-    component_types: Vec<Symbol>,
+/// What a [`SystemParam`] knows about the system it is fetched for
+#[derive(Clone, Debug, Default)]
+pub struct SystemMeta {
+    /// Name the system's state is saved under, see [`System::set_name`]
+    pub name: Option<Symbol>,
+    /// Change tick at which the system last ran, or 0 if it never did
+    ///
+    /// Named systems take it from the world, so it carries over from the
+    /// previous contract invocation.
+    pub last_run: u64,
 }
 
-impl Query {
-    /// Create a new query
-    pub fn new(component_types: Vec<Symbol>) -> Self {
-        Self { component_types }
-    }
+/// A value a function system can take as an argument
+///
+/// Parameters are owned snapshots: they are fetched from the world before the
+/// system runs, and whatever they hand back through their `State` is applied
+/// to the world after it returns.
+pub trait SystemParam: Sized {
+    /// State kept by the system between runs, such as an event cursor
+    type Local: Default;
+    /// Shared state kept by the system while the parameter is in use
+    type State;
 
-    /// Add a component type to the query
-    pub fn with_component(mut self, component_type: Symbol) -> Self {
-        self.component_types.push_back(component_type);
-        self
-    }
+    /// Fetch the parameter from the world
+    fn fetch(world: &World, local: &mut Self::Local, system: &SystemMeta) -> (Self, Self::State);
 
-    /// Execute the query on a world
-    pub fn execute(&self, world: &World) -> Vec<EntityId> {
-        // Convert Vec<Symbol> to &[Symbol] by creating a slice
-        // This is a limitation of the Soroban SDK - we can't easily convert Vec to slice
-        // For now, we'll use a different approach
-        let env = soroban_sdk::Env::default();
-        let mut results = Vec::new(&env);
-        for entity in world.iter_entities() {
-            let mut has_all_components = true;
-            for i in 0..self.component_types.len() {
-                let ctype = self.component_types.get(i).unwrap();
-                if !entity.has_component(&ctype) {
-                    has_all_components = false;
-                    break;
-                }
-            }
-            if has_all_components {
-                results.push_back(entity.id());
-            }
-        }
-        results
-    }
+    /// Apply changes made through the parameter to the world
+    fn apply(state: Self::State, world: &mut World);
 }
 
-/// Query state for tracking query results
-pub struct QueryState {
-    query: Query,
-    last_results: Vec<EntityId>,
-}
-
-impl QueryState {
-    /// Create a new query state
-    pub fn new(query: Query) -> Self {
-        let env = soroban_sdk::Env::default();
-        Self {
-            query,
-            last_results: Vec::new(&env),
-        }
-    }
-
-    /// Execute the query and update state
-    pub fn execute(&mut self, world: &World) -> &Vec<EntityId> {
-        self.last_results = self.query.execute(world);
-        &self.last_results
-    }
-
-    /// Get the last query results
-    pub fn results(&self) -> &Vec<EntityId> {
-        &self.last_results
-    }
-
-    /// Check if the query has any results
-    pub fn is_empty(&self) -> bool {
-        self.last_results.is_empty()
-    }
-
-    /// Get the number of results
-    pub fn len(&self) -> usize {
-        self.last_results.len().try_into().unwrap()
-    }
-}
+/// Cell a parameter hands its value back through when it is dropped
+type Slot<T> = Rc<RefCell<Option<T>>>;
 
 /// Conversion trait to turn something into a system
-pub trait IntoSystem<In, Out> {
+///
+/// `Marker` only distinguishes the blanket impls: functions taking
+/// `(&mut World, In)` and functions taking [`SystemParam`]s.
+pub trait IntoSystem<In, Out, Marker> {
     /// The type of system that this converts into
     type System: System<In = In, Out = Out>;
 
@@ -148,7 +108,10 @@ where
     }
 }
 
-impl<F, In, Out> IntoSystem<In, Out> for F
+/// Marker for systems written as `FnMut(&mut World, In) -> Out`
+pub struct WorldFunction;
+
+impl<F, In, Out> IntoSystem<In, Out, WorldFunction> for F
 where
     F: FnMut(&mut World, In) -> Out,
 {
@@ -159,71 +122,429 @@ where
     }
 }
 
-/// System parameter for querying entities
-pub struct QueryParam {
-    query: Query,
+/// A function whose arguments are all [`SystemParam`]s
+pub trait SystemParamFunction<Params: SystemParam> {
+    /// The return type of the function
+    type Out;
+
+    /// Call the function with fetched parameters
+    fn call(&mut self, params: Params) -> Self::Out;
+}
+
+/// A system built from a function taking [`SystemParam`]s
+pub struct ParamFunctionSystem<F, Params: SystemParam> {
+    function: F,
+    local: Params::Local,
+    meta: SystemMeta,
+    _phantom: core::marker::PhantomData<fn() -> Params>,
 }
 
-impl QueryParam {
-    /// Create a new query parameter
-    pub fn new(component_types: Vec<Symbol>) -> Self {
+impl<F, Params> ParamFunctionSystem<F, Params>
+where
+    Params: SystemParam,
+    F: SystemParamFunction<Params>,
+{
+    /// Create a new parameter function system
+    pub fn new(function: F) -> Self {
         Self {
-            query: Query::new(component_types),
+            function,
+            local: Params::Local::default(),
+            meta: SystemMeta::default(),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<F, Params> System for ParamFunctionSystem<F, Params>
+where
+    Params: SystemParam,
+    F: SystemParamFunction<Params>,
+{
+    type In = ();
+    type Out = F::Out;
+
+    fn run(&mut self, world: &mut World, _input: Self::In) -> Self::Out {
+        if let Some(name) = &self.meta.name {
+            self.meta.last_run = world.system_last_run(name);
+        }
+        let this_run = world.change_tick();
+        let (params, state) = Params::fetch(world, &mut self.local, &self.meta);
+        let out = self.function.call(params);
+        Params::apply(state, world);
+        self.meta.last_run = this_run;
+        if let Some(name) = &self.meta.name {
+            world.set_system_last_run(name, this_run);
+        }
+        // Later changes must stamp a newer tick than the one this run saw
+        world.increment_change_tick();
+        out
+    }
+
+    fn set_name(&mut self, name: Symbol) {
+        self.meta.name = Some(name);
+    }
+}
+
+impl<F, Out, Params> IntoSystem<(), Out, fn(Params) -> Out> for F
+where
+    Params: SystemParam,
+    F: SystemParamFunction<Params, Out = Out>,
+{
+    type System = ParamFunctionSystem<F, Params>;
+
+    fn into_system(self) -> Self::System {
+        ParamFunctionSystem::new(self)
+    }
+}
+
+macro_rules! impl_system_param_tuple {
+    ($($param:ident),*) => {
+        impl<$($param: SystemParam),*> SystemParam for ($($param,)*) {
+            type Local = ($($param::Local,)*);
+            type State = ($($param::State,)*);
+
+            #[allow(non_snake_case, unused_variables)]
+            fn fetch(
+                world: &World,
+                local: &mut Self::Local,
+                system: &SystemMeta,
+            ) -> (Self, Self::State) {
+                let ($($param,)*) = local;
+                $(let $param = $param::fetch(world, $param, system);)*
+                (($($param.0,)*), ($($param.1,)*))
+            }
+
+            #[allow(non_snake_case, unused_variables)]
+            fn apply(state: Self::State, world: &mut World) {
+                let ($($param,)*) = state;
+                $($param::apply($param, world);)*
+            }
+        }
+
+        impl<Func, Out, $($param: SystemParam),*> SystemParamFunction<($($param,)*)> for Func
+        where
+            Func: FnMut($($param),*) -> Out,
+        {
+            type Out = Out;
+
+            #[allow(non_snake_case)]
+            fn call(&mut self, params: ($($param,)*)) -> Out {
+                let ($($param,)*) = params;
+                self($($param),*)
+            }
+        }
+    };
+}
+
+impl_system_param_tuple!();
+impl_system_param_tuple!(P0);
+impl_system_param_tuple!(P0, P1);
+impl_system_param_tuple!(P0, P1, P2);
+impl_system_param_tuple!(P0, P1, P2, P3);
+impl_system_param_tuple!(P0, P1, P2, P3, P4);
+impl_system_param_tuple!(P0, P1, P2, P3, P4, P5);
+impl_system_param_tuple!(P0, P1, P2, P3, P4, P5, P6);
+impl_system_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7);
+
+/// System parameter yielding the components in `Q` for every matching entity
+///
+/// Changes made through [`QueryParam::iter_mut`] or [`QueryParam::get_mut`]
+/// are written back after the system returns. The optional filter `F`, such
+/// as [`Changed<Position>`](crate::change::Changed), keeps only entities
+/// whose components changed since the system last ran.
+pub struct QueryParam<Q: QueryData, F: ChangeFilter = ()> {
+    rows: StdVec<(EntityId, Q)>,
+    slot: Slot<StdVec<(EntityId, Q)>>,
+    _filter: PhantomData<F>,
+}
+
+impl<Q: QueryData, F: ChangeFilter> QueryParam<Q, F> {
+    /// Iterate over matching entities and their components
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, &Q)> {
+        self.rows.iter().map(|(entity_id, data)| (*entity_id, data))
+    }
+
+    /// Iterate mutably over matching entities and their components
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EntityId, &mut Q)> {
+        self.rows
+            .iter_mut()
+            .map(|(entity_id, data)| (*entity_id, data))
+    }
+
+    /// Get the components of a specific entity
+    pub fn get(&self, entity_id: EntityId) -> Option<&Q> {
+        self.rows
+            .iter()
+            .find(|(id, _)| *id == entity_id)
+            .map(|(_, data)| data)
+    }
+
+    /// Get the components of a specific entity mutably
+    pub fn get_mut(&mut self, entity_id: EntityId) -> Option<&mut Q> {
+        self.rows
+            .iter_mut()
+            .find(|(id, _)| *id == entity_id)
+            .map(|(_, data)| data)
+    }
+
+    /// Get the number of matching entities
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if no entities matched
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl<Q: QueryData, F: ChangeFilter> Drop for QueryParam<Q, F> {
+    fn drop(&mut self) {
+        *self.slot.borrow_mut() = Some(core::mem::take(&mut self.rows));
+    }
+}
+
+impl<Q: QueryData, F: ChangeFilter> SystemParam for QueryParam<Q, F> {
+    type Local = ();
+    type State = Slot<StdVec<(EntityId, Q)>>;
+
+    fn fetch(world: &World, _local: &mut (), system: &SystemMeta) -> (Self, Self::State) {
+        let since = system.last_run;
+        let rows = crate::query::Query::new(world.env())
+            .requiring::<Q>(world.env())
+            .execute(world)
+            .iter()
+            .filter(|&entity_id| F::matches(world, entity_id, since))
+            .filter_map(|entity_id| Some((entity_id, Q::fetch(world, entity_id)?)))
+            .collect();
+        let slot = Slot::default();
+        (
+            Self {
+                rows,
+                slot: slot.clone(),
+                _filter: PhantomData,
+            },
+            slot,
+        )
+    }
+
+    fn apply(state: Self::State, world: &mut World) {
+        if let Some(rows) = state.borrow_mut().take() {
+            for (entity_id, data) in rows {
+                data.write_back(world, entity_id);
+            }
+        }
+    }
+}
+
+/// Read-only system parameter holding a decoded resource
+///
+/// Fetching panics if the resource is missing; use `Option<Res<T>>` for
+/// resources that may not exist.
+pub struct Res<T: ResourceTrait> {
+    value: T,
+    ticks: Option<ComponentTicks>,
+    last_run: u64,
+}
+
+impl<T: ResourceTrait> Res<T> {
+    /// Take the decoded resource
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Check if the resource was added since the system last ran
+    pub fn is_added(&self) -> bool {
+        self.ticks
+            .is_some_and(|ticks| ticks.is_added(self.last_run))
+    }
+
+    /// Check if the resource was added or changed since the system last ran
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .is_some_and(|ticks| ticks.is_changed(self.last_run))
+    }
+}
+
+impl<T: ResourceTrait> Deref for Res<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: ResourceTrait> SystemParam for Option<Res<T>> {
+    type Local = ();
+    type State = ();
+
+    fn fetch(world: &World, _local: &mut (), system: &SystemMeta) -> (Self, Self::State) {
+        let value = world.resource::<T>().map(|value| Res {
+            value,
+            ticks: world.resource_ticks(&T::resource_type()),
+            last_run: system.last_run,
+        });
+        (value, ())
+    }
+
+    fn apply(_state: Self::State, _world: &mut World) {}
+}
+
+impl<T: ResourceTrait> SystemParam for Res<T> {
+    type Local = ();
+    type State = ();
+
+    fn fetch(world: &World, local: &mut (), system: &SystemMeta) -> (Self, Self::State) {
+        match Option::<Res<T>>::fetch(world, local, system) {
+            (Some(res), state) => (res, state),
+            (None, _) => panic!("missing resource {}", core::any::type_name::<T>()),
         }
     }
 
-    /// Add a component type to the query
-    pub fn with_component(mut self, component_type: Symbol) -> Self {
-        self.query = self.query.with_component(component_type);
-        self
+    fn apply(_state: Self::State, _world: &mut World) {}
+}
+
+/// Mutable system parameter holding a decoded resource
+///
+/// The resource is written back after the system returns. Fetching panics if
+/// the resource is missing; use `Option<ResMut<T>>` for resources that may
+/// not exist.
+pub struct ResMut<T: ResourceTrait> {
+    value: Option<T>,
+    slot: Slot<T>,
+    ticks: Option<ComponentTicks>,
+    last_run: u64,
+}
+
+impl<T: ResourceTrait> ResMut<T> {
+    /// Check if the resource was added since the system last ran
+    pub fn is_added(&self) -> bool {
+        self.ticks
+            .is_some_and(|ticks| ticks.is_added(self.last_run))
+    }
+
+    /// Check if the resource was added or changed since the system last ran
+    ///
+    /// Changes made through this parameter count only once written back.
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .is_some_and(|ticks| ticks.is_changed(self.last_run))
     }
 }
 
-impl SystemParam for QueryParam {
-    type Param = Self;
-    type Fetch = Vec<EntityId>;
+impl<T: ResourceTrait> Deref for ResMut<T> {
+    type Target = T;
 
-    fn fetch(world: &World) -> Self::Fetch {
-        // This is a simplified implementation
-        // In a real system, you'd have more sophisticated query execution
-        let env = soroban_sdk::Env::default();
-        Vec::new(&env)
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
     }
+}
 
-    fn fetch_mut(world: &mut World) -> Self::Fetch {
-        Self::fetch(world)
+impl<T: ResourceTrait> DerefMut for ResMut<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
     }
 }
 
-/// System parameter for accessing resources
-pub struct ResourceParam {
-    resource_type: Symbol,
+impl<T: ResourceTrait> Drop for ResMut<T> {
+    fn drop(&mut self) {
+        *self.slot.borrow_mut() = self.value.take();
+    }
 }
 
-impl ResourceParam {
-    /// Create a new resource parameter
-    pub fn new(resource_type: Symbol) -> Self {
-        Self { resource_type }
+impl<T: ResourceTrait> SystemParam for Option<ResMut<T>> {
+    type Local = ();
+    type State = Slot<T>;
+
+    fn fetch(world: &World, _local: &mut (), system: &SystemMeta) -> (Self, Self::State) {
+        let slot = Slot::default();
+        let value = world.resource::<T>().map(|value| ResMut {
+            value: Some(value),
+            slot: slot.clone(),
+            ticks: world.resource_ticks(&T::resource_type()),
+            last_run: system.last_run,
+        });
+        (value, slot)
+    }
+
+    fn apply(state: Self::State, world: &mut World) {
+        if let Some(value) = state.borrow_mut().take() {
+            // Only write back real changes, so they are not detected falsely
+            let data = value.serialize(world.env());
+            let current = world.get_resource(&T::resource_type());
+            if current.is_some_and(|current| current.data() != &data) {
+                world.insert_resource(value);
+            }
+        }
     }
 }
 
-impl SystemParam for ResourceParam {
-    type Param = Self;
-    type Fetch = Option<crate::resource::Resource>;
+impl<T: ResourceTrait> SystemParam for ResMut<T> {
+    type Local = ();
+    type State = Slot<T>;
+
+    fn fetch(world: &World, local: &mut (), system: &SystemMeta) -> (Self, Self::State) {
+        match Option::<ResMut<T>>::fetch(world, local, system) {
+            (Some(res), state) => (res, state),
+            (None, _) => panic!("missing resource {}", core::any::type_name::<T>()),
+        }
+    }
+
+    fn apply(state: Self::State, world: &mut World) {
+        Option::<ResMut<T>>::apply(state, world)
+    }
+}
 
-    fn fetch(world: &World) -> Self::Fetch {
-        // This is a simplified implementation
-        // In a real system, you'd have access to the parameter instance
-        None
+impl<E: EventTrait> SystemParam for EventReader<E> {
+    /// Cursor of an unnamed system
+    type Local = EventCursor<E>;
+    /// Advanced cursor of a named system, saved to the world
+    type State = Option<(Symbol, EventCursor<E>)>;
+
+    fn fetch(
+        world: &World,
+        cursor: &mut EventCursor<E>,
+        system: &SystemMeta,
+    ) -> (Self, Self::State) {
+        match &system.name {
+            Some(name) => {
+                let mut cursor = world.event_cursor::<E>(name);
+                let events = world.read_events(&mut cursor);
+                (EventReader::new(events), Some((name.clone(), cursor)))
+            }
+            None => (EventReader::new(world.read_events(cursor)), None),
+        }
     }
 
-    fn fetch_mut(world: &mut World) -> Self::Fetch {
-        Self::fetch(world)
+    fn apply(state: Self::State, world: &mut World) {
+        if let Some((name, cursor)) = state {
+            world.set_event_cursor(&name, cursor);
+        }
+    }
+}
+
+impl<E: EventTrait> SystemParam for EventWriter<E> {
+    type Local = ();
+    type State = Rc<RefCell<Vec<Event>>>;
+
+    fn fetch(world: &World, _local: &mut (), _system: &SystemMeta) -> (Self, Self::State) {
+        let writer = EventWriter::new(world.env());
+        let events = writer.events.clone();
+        (writer, events)
+    }
+
+    fn apply(state: Self::State, world: &mut World) {
+        for event in state.borrow().iter() {
+            world.send_event(event);
+        }
     }
 }
 
 // Example systems
-/// Movement system for updating entity positions
+/// Collision detection system, from [`physics2d`](crate::physics2d)
+pub use crate::physics2d::CollisionSystem;
+
+/// Movement system adding each entity's [`Velocity`] to its [`Position`]
 pub struct MovementSystem;
 
 impl System for MovementSystem {
@@ -231,134 +552,393 @@ impl System for MovementSystem {
     type Out = ();
 
     fn run(&mut self, world: &mut World, _input: Self::In) -> Self::Out {
-        // Example: Find all entities with position and velocity components
-        let entities_with_movement =
-            world.query_entities(&[symbol_short!("position"), symbol_short!("velocity")]);
-
-        for i in 0..entities_with_movement.len() {
-            let entity_id = entities_with_movement.get(i).unwrap();
-            // In a real implementation, you'd:
-            // 1. Get the position and velocity components
-            // 2. Update the position based on velocity
-            // 3. Apply any constraints (bounds, collision, etc.)
-            // For now, we'll just mark that we processed this entity
-        }
+        world.query_mut::<(Position, Velocity), _>(|_, (position, velocity)| {
+            position.x = position.x.saturating_add(velocity.x);
+            position.y = position.y.saturating_add(velocity.y);
+        });
     }
 }
 
-/// Collision detection system
-pub struct CollisionSystem;
-
-impl System for CollisionSystem {
-    type In = ();
-    type Out = ();
+/// Health system applying [`DamageEvent`]s to [`Health`] components
+///
+/// Entities whose health reaches zero get a [`DeathEvent`] and are despawned.
+/// Damage to entities without health, or that no longer exist, is ignored.
+/// Each event is applied once, even across contract invocations: the system
+/// keeps its cursor in the world under its name, `health` unless renamed
+/// with [`System::set_name`].
+pub struct HealthSystem {
+    name: Symbol,
+}
 
-    fn run(&mut self, world: &mut World, _input: Self::In) -> Self::Out {
-        let entities_with_collision =
-            world.query_entities(&[symbol_short!("position"), symbol_short!("collision")]);
-
-        for i in 0..entities_with_collision.len() {
-            for j in (i + 1)..entities_with_collision.len() {
-                let entity_a = entities_with_collision.get(i).unwrap();
-                let entity_b = entities_with_collision.get(j).unwrap();
-                // Generate collision event
-                let collision_event = crate::event::CollisionEvent::new(
-                    entity_a.id(),
-                    entity_b.id(),
-                    symbol_short!("physical"),
-                );
-                let env = soroban_sdk::Env::default();
-                let event_data = collision_event.serialize(&env);
-                let event = crate::event::Event::new(symbol_short!("collision"), event_data);
-                world.send_event(event);
-            }
+impl HealthSystem {
+    pub fn new() -> Self {
+        Self {
+            name: symbol_short!("health"),
         }
     }
 }
 
-/// Health system for managing entity health
-pub struct HealthSystem;
+impl Default for HealthSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl System for HealthSystem {
     type In = ();
     type Out = ();
 
     fn run(&mut self, world: &mut World, _input: Self::In) -> Self::Out {
-        let damage_events = world.get_events(&symbol_short!("damage"));
-        let env = soroban_sdk::Env::default();
-        for i in 0..damage_events.len() {
-            let event = damage_events.get(i).unwrap();
-            if let Some(damage_event) = DamageEvent::deserialize(&env, event.data()) {
-                let target_entity = EntityId::new(damage_event.target_entity, 0);
-                // In a real implementation, you'd:
-                // 1. Get the health component from the target entity
-                // 2. Apply the damage
-                // 3. Check if the entity should be destroyed
-                // 4. Update the health component
+        for damage in world.read_events_as::<DamageEvent>(&self.name) {
+            let target = EntityId::from_bits(damage.target_entity);
+            let Some(mut health) = world.get::<Health>(target) else {
+                continue;
+            };
+            health.damage(damage.damage_amount);
+            if health.is_dead() {
+                world.send(DeathEvent::new(damage.target_entity));
+                world.despawn(target);
+            } else {
+                world.insert(target, health);
             }
         }
     }
+
+    fn set_name(&mut self, name: Symbol) {
+        self.name = name;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::Env;
-
-    #[test]
-    fn test_query_creation() {
-        let env = Env::default();
-        let mut component_types = Vec::new(&env);
-        component_types.push_back(symbol_short!("position"));
-        component_types.push_back(symbol_short!("velocity"));
-        let query = Query::new(component_types);
-
-        let world = World::new();
-        let results = query.execute(&world);
-        assert_eq!(results.len(), 0);
-    }
-
-    #[test]
-    fn test_query_state() {
-        let env = Env::default();
-        let mut component_types = Vec::new(&env);
-        component_types.push_back(symbol_short!("position"));
-        let query = Query::new(component_types);
-        let mut query_state = QueryState::new(query);
-
-        let world = World::new();
-        let results = query_state.execute(&world);
-        assert_eq!(results.len(), 0);
-        assert!(query_state.is_empty());
+    use crate::change::{Added, Changed};
+    use crate::components::Collider;
+    use crate::event::CollisionEvent;
+    use crate::resource::GameState;
+    use soroban_sdk::{contract, symbol_short, Env};
+
+    #[contract]
+    pub struct TestContract;
+
+    fn movement(
+        mut query: QueryParam<(Position, Velocity)>,
+        state: Res<GameState>,
+        mut damage: EventWriter<DamageEvent>,
+    ) -> u32 {
+        let mut moved = 0;
+        for (entity_id, (position, velocity)) in query.iter_mut() {
+            position.x += velocity.x * state.level;
+            position.y += velocity.y * state.level;
+            if position.x > 10 {
+                damage.send(DamageEvent::new(
+                    entity_id.to_bits(),
+                    1,
+                    symbol_short!("bounds"),
+                ));
+            }
+            moved += 1;
+        }
+        moved
     }
 
     #[test]
     fn test_function_system() {
-        let mut system = FunctionSystem::new(|world: &mut World, input: i32| {
+        let env = Env::default();
+        let mut system = FunctionSystem::new(|_world: &mut World, input: i32| {
             // Simple system that just returns the input
             input
         });
 
-        let mut world = World::new();
+        let mut world = World::new(&env);
         let result = system.run(&mut world, 42);
         assert_eq!(result, 42);
     }
 
     #[test]
     fn test_movement_system() {
+        let env = Env::default();
         let mut system = MovementSystem;
-        let mut world = World::new();
+        let mut world = World::new(&env);
+        let moving = world.spawn_empty().id();
+        world.insert(moving, Position::new(1, 2));
+        world.insert(moving, Velocity::new(3, -4));
+        let still = world.spawn_empty().id();
+        world.insert(still, Position::new(5, 5));
 
-        // This should run without errors
         system.run(&mut world, ());
+        system.run(&mut world, ());
+        assert_eq!(world.get::<Position>(moving), Some(Position::new(7, -6)));
+        assert_eq!(world.get::<Position>(still), Some(Position::new(5, 5)));
     }
 
     #[test]
     fn test_collision_system() {
-        let mut system = CollisionSystem;
-        let mut world = World::new();
+        let env = Env::default();
+        let mut system = CollisionSystem::default();
+        let mut world = World::new(&env);
+        let mut spawn = |x: i32| {
+            let entity = world.spawn_empty().id();
+            world.insert(entity, Position::new(x, 0));
+            world.insert(entity, Collider::new(2, 2));
+            entity
+        };
+        let a = spawn(0);
+        let b = spawn(3);
+        let _far = spawn(20);
+        let ghost = world.spawn_empty().id();
+        world.insert(ghost, Position::new(0, 0));
 
-        // This should run without errors
         system.run(&mut world, ());
+        let collisions = world.events::<CollisionEvent>();
+        assert_eq!(collisions.len(), 1);
+        let collision = collisions.iter().next().unwrap();
+        assert_eq!(collision.entity_a, a.to_bits());
+        assert_eq!(collision.entity_b, b.to_bits());
+    }
+
+    #[test]
+    fn test_health_system() {
+        let env = Env::default();
+        let mut system = HealthSystem::new();
+        let mut world = World::new(&env);
+        let tank = world.spawn_empty().id();
+        world.insert(tank, Health::new(10));
+        let scout = world.spawn_empty().id();
+        world.insert(scout, Health::new(3));
+
+        world.send(DamageEvent::new(tank.to_bits(), 4, symbol_short!("fire")));
+        world.send(DamageEvent::new(scout.to_bits(), 3, symbol_short!("fire")));
+        system.run(&mut world, ());
+        // Already applied events are not applied again
+        system.run(&mut world, ());
+
+        assert_eq!(world.get::<Health>(tank).unwrap().current, 6);
+        assert!(!world.exists(scout));
+        let deaths = world.events::<DeathEvent>();
+        assert_eq!(deaths.len(), 1);
+        assert_eq!(deaths.iter().next().unwrap().entity, scout.to_bits());
+    }
+
+    #[test]
+    fn test_health_system_applies_damage_once_across_invocations() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let mut world = World::new(&env);
+            let scout = world.spawn_empty().id();
+            world.insert(scout, Health::new(5));
+            world.send(DamageEvent::new(scout.to_bits(), 3, symbol_short!("fire")));
+            HealthSystem::new().run(&mut world, ());
+            world.update_events();
+            world.save(&symbol_short!("world"));
+
+            // The next invocation rebuilds the system; the damage stays
+            // buffered but is not applied again
+            let mut world = World::load(&env, &symbol_short!("world")).unwrap();
+            HealthSystem::new().run(&mut world, ());
+            assert_eq!(world.get::<Health>(scout).unwrap().current, 2);
+
+            world.send(DamageEvent::new(scout.to_bits(), 2, symbol_short!("fire")));
+            HealthSystem::new().run(&mut world, ());
+            world.save(&symbol_short!("world"));
+
+            let mut world = World::load(&env, &symbol_short!("world")).unwrap();
+            HealthSystem::new().run(&mut world, ());
+            assert!(!world.exists(scout));
+            assert_eq!(world.events::<DeathEvent>().len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_param_function_system() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let mut state = GameState::new();
+        state.next_level();
+        world.insert_resource(state);
+
+        let fast = world.spawn_empty().id();
+        world.insert(fast, Position::new(0, 0));
+        world.insert(fast, Velocity::new(6, 1));
+        let still = world.spawn_empty().id();
+        world.insert(still, Position::new(3, 3));
+
+        let mut system = movement.into_system();
+        assert_eq!(system.run(&mut world, ()), 1);
+        assert_eq!(world.get::<Position>(fast), Some(Position::new(12, 2)));
+        assert_eq!(world.get::<Position>(still), Some(Position::new(3, 3)));
+
+        let events = world.get_events(&DamageEvent::event_type());
+        assert_eq!(events.len(), 1);
+        let event = DamageEvent::deserialize(&env, events.get(0).unwrap().data()).unwrap();
+        assert_eq!(EntityId::from_bits(event.target_entity), fast);
+    }
+
+    #[test]
+    fn test_event_reader_sees_each_event_once() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let mut system = (|reader: EventReader<DamageEvent>| {
+            reader.read().map(|event| event.damage_amount).sum::<i32>()
+        })
+        .into_system();
+
+        world.send(DamageEvent::new(1, 5, symbol_short!("fire")));
+        world.send(DamageEvent::new(1, 7, symbol_short!("fire")));
+        assert_eq!(system.run(&mut world, ()), 12);
+        assert_eq!(system.run(&mut world, ()), 0);
+
+        world.update_events();
+        world.send(DamageEvent::new(1, 1, symbol_short!("fire")));
+        assert_eq!(system.run(&mut world, ()), 1);
+
+        // A new reader still sees everything buffered
+        let mut fresh = (|reader: EventReader<DamageEvent>| reader.len()).into_system();
+        assert_eq!(fresh.run(&mut world, ()), 3);
+    }
+
+    #[test]
+    fn test_named_event_reader_survives_save_and_load() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let total = |reader: EventReader<DamageEvent>| {
+            reader.read().map(|event| event.damage_amount).sum::<i32>()
+        };
+        // Each invocation rebuilds the system, as contracts do
+        let invoke = |send: i32| {
+            let mut world =
+                World::load(&env, &symbol_short!("world")).unwrap_or_else(|| World::new(&env));
+            world.send(DamageEvent::new(1, send, symbol_short!("fire")));
+            let mut system = total.into_system();
+            system.set_name(symbol_short!("total"));
+            let read = system.run(&mut world, ());
+            world.update_events();
+            world.save(&symbol_short!("world"));
+            read
+        };
+
+        env.as_contract(&contract_id, || {
+            assert_eq!(invoke(5), 5);
+            assert_eq!(invoke(7), 7);
+            assert_eq!(invoke(1), 1);
+        });
+    }
+
+    #[test]
+    fn test_res_mut_writes_back() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world.insert_resource(GameState::new());
+
+        let mut system = (|mut state: ResMut<GameState>| state.increment_score(5)).into_system();
+        system.run(&mut world, ());
+        system.run(&mut world, ());
+
+        assert_eq!(world.resource::<GameState>().unwrap().score, 10);
+    }
+
+    #[test]
+    fn test_optional_resource_param() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+
+        let mut system = (|state: Option<Res<GameState>>| state.is_some()).into_system();
+        assert!(!system.run(&mut world, ()));
+        world.insert_resource(GameState::new());
+        assert!(system.run(&mut world, ()));
+    }
+
+    #[test]
+    fn test_changed_query_param() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.insert(a, Position::new(0, 0));
+        world.insert(b, Position::new(0, 0));
+
+        let mut changed =
+            (|query: QueryParam<Position, Changed<Position>>| query.len()).into_system();
+        // Moves `b`, then sees its own change no more
+        let mut mover = (|mut query: QueryParam<Position>| {
+            if let Some((_, position)) = query.iter_mut().nth(1) {
+                position.x += 1;
+            }
+        })
+        .into_system();
+
+        assert_eq!(changed.run(&mut world, ()), 2);
+        assert_eq!(changed.run(&mut world, ()), 0);
+        mover.run(&mut world, ());
+        assert_eq!(changed.run(&mut world, ()), 1);
+        world.insert(a, Position::new(5, 5));
+        assert_eq!(changed.run(&mut world, ()), 1);
+        assert_eq!(changed.run(&mut world, ()), 0);
+
+        let mut added = (|query: QueryParam<Position, Added<Position>>| query.len()).into_system();
+        assert_eq!(added.run(&mut world, ()), 2);
+        let c = world.spawn_empty().id();
+        world.insert(c, Position::new(0, 0));
+        assert_eq!(added.run(&mut world, ()), 1);
+    }
+
+    #[test]
+    fn test_named_system_change_detection_survives_save_and_load() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let watch = |query: QueryParam<Position, Changed<Position>>, state: Res<GameState>| {
+            (query.len(), state.is_changed())
+        };
+        // Each invocation rebuilds the system, as contracts do
+        let invoke = |world: &mut World| {
+            let mut system = watch.into_system();
+            system.set_name(symbol_short!("watch"));
+            system.run(world, ())
+        };
+
+        env.as_contract(&contract_id, || {
+            let mut world = World::new(&env);
+            let a = world.spawn_empty().id();
+            world.insert(a, Position::new(0, 0));
+            let b = world.spawn_empty().id();
+            world.insert(b, Position::new(0, 0));
+            world.insert_resource(GameState::new());
+            assert_eq!(invoke(&mut world), (2, true));
+            world.save(&symbol_short!("world"));
+
+            let mut world = World::load(&env, &symbol_short!("world")).unwrap();
+            assert_eq!(invoke(&mut world), (0, false));
+            world.insert(b, Position::new(1, 0));
+            world.save(&symbol_short!("world"));
+
+            let mut world = World::load(&env, &symbol_short!("world")).unwrap();
+            assert_eq!(invoke(&mut world), (1, false));
+        });
+    }
+
+    #[test]
+    fn test_res_is_changed() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world.insert_resource(GameState::new());
+
+        let mut watcher = (|state: Res<GameState>| state.is_changed()).into_system();
+        let mut scorer = (|mut state: ResMut<GameState>| {
+            let changed = state.is_changed();
+            state.increment_score(1);
+            changed
+        })
+        .into_system();
+        let mut idle = (|state: ResMut<GameState>| state.score).into_system();
+
+        assert!(watcher.run(&mut world, ()));
+        assert!(!watcher.run(&mut world, ()));
+        assert!(scorer.run(&mut world, ()));
+        assert!(watcher.run(&mut world, ()));
+        // Untouched `ResMut`s are not written back
+        idle.run(&mut world, ());
+        assert!(!watcher.run(&mut world, ()));
+        assert!(!scorer.run(&mut world, ()));
     }
 }
@@ -0,0 +1,50 @@
+//! Helpers for testing contracts built on Cougr
+//!
+//! Available in this crate's tests and to downstream crates through the
+//! `testutils` feature.
+
+use crate::event::PublishEvent;
+use soroban_sdk::testutils::Events as _;
+use soroban_sdk::{vec, Address, Env, Val, Vec};
+
+/// Get the topics and data of every contract event `contract` has published
+pub fn published_events(env: &Env, contract: &Address) -> Vec<(Vec<Val>, Val)> {
+    let mut events = Vec::new(env);
+    for (address, topics, data) in env.events().all().iter() {
+        if address == *contract {
+            events.push_back((topics, data));
+        }
+    }
+    events
+}
+
+/// Count the contract events of type `E` that `contract` has published
+pub fn published_count<E: PublishEvent>(env: &Env, contract: &Address) -> u32 {
+    let event_type = E::event_type();
+    let mut count = 0;
+    for (topics, _) in published_events(env, contract).iter() {
+        let first = topics.get(0).map(|topic| vec![env, topic]);
+        if first == Some(vec![env, event_type.to_val()]) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Assert that `contract` published `event` as a contract event
+///
+/// Panics listing the published events if no event has matching topics and
+/// data.
+pub fn assert_published<E: PublishEvent>(env: &Env, contract: &Address, event: &E) {
+    let expected = vec![env, (event.topics(env), event.publish_data(env))];
+    let published = published_events(env, contract);
+    let found = published
+        .iter()
+        .any(|published| vec![env, published] == expected);
+    assert!(
+        found,
+        "event {:?} was not published; published events: {:?}",
+        E::event_type(),
+        published
+    );
+}
@@ -1,48 +1,100 @@
-use crate::component::{Component, ComponentRegistry};
-use crate::entity::{Entity, EntityId, EntityIterator, EntityIteratorMut, EntityManager};
-use crate::event::Event;
-use crate::resource::Resource;
+use crate::change::ChangeTicks;
+use crate::component::{Component, ComponentRegistry, ComponentTrait};
+use crate::entity::{
+    Entity, EntityId, EntityIterator, EntityIteratorMut, EntityManager, EntityMut,
+};
+use crate::event::{
+    publish_as, Event, EventCursor, EventPublisher, EventQueue, EventTrait, Events, PublishEvent,
+};
+use crate::observers::Observer;
+use crate::query::{Query, QueryData};
+use crate::relation::Relation;
+use crate::resource::{Resource, ResourceTrait};
 use crate::storage::Storage;
-use soroban_sdk::{contracttype, Symbol, Vec};
+use alloc::vec::Vec as StdVec;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use soroban_sdk::{Bytes, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
 
 /// The main ECS world that contains all entities, components, and systems
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct World {
+    /// The Soroban environment every container in this world is bound to
+    env: Env,
     /// Entity manager for handling entity lifecycle
     pub entities: EntityManager,
     /// Component registry for managing component types
     pub components: ComponentRegistry,
     /// Component storage system
     pub storage: Storage,
-    /// Resources (global state)
-    pub resources: Vec<Resource>,
-    /// Event system
-    pub events: Vec<Event>,
+    /// Encoded resources (global state), keyed by resource type
+    pub resources: Map<Symbol, Bytes>,
+    /// Double-buffered event queues, keyed by event type
+    pub events: Map<Symbol, EventQueue>,
+    /// Entity relationships, keyed by relation kind
+    pub relations: Map<Symbol, Relation>,
+    /// Change detection ticks of components and resources
+    pub change_ticks: ChangeTicks,
+    /// Number of completed schedule runs
+    tick: u64,
+    /// Event types published as contract events by [`World::flush`]
+    ///
+    /// Not saved: contracts mark event types on every invocation, as they
+    /// build their schedules.
+    publishers: StdVec<(Symbol, EventPublisher)>,
+    /// Observers of triggered events, in registration order; not saved
+    pub(crate) observers: StdVec<Observer>,
+    /// Id of the next observer to be registered
+    pub(crate) next_observer_id: u32,
 }
 
 impl World {
-    /// Create a new empty world
-    pub fn new() -> Self {
-        let env = soroban_sdk::Env::default();
+    /// Create a new empty world bound to `env`
+    pub fn new(env: &Env) -> Self {
         Self {
-            entities: EntityManager::new(),
+            env: env.clone(),
+            entities: EntityManager::new(env),
             components: ComponentRegistry::new(),
-            storage: Storage::new(),
-            resources: Vec::new(&env),
-            events: Vec::new(&env),
+            storage: Storage::new(env),
+            resources: Map::new(env),
+            events: Map::new(env),
+            relations: Map::new(env),
+            change_ticks: ChangeTicks::new(env),
+            tick: 0,
+            publishers: StdVec::new(),
+            observers: StdVec::new(),
+            next_observer_id: 0,
         }
     }
 
+    /// Get the environment this world is bound to
+    pub fn env(&self) -> &Env {
+        &self.env
+    }
+
+    /// Get the number of completed schedule runs
+    ///
+    /// The tick is saved with the world, so it keeps counting across contract
+    /// invocations.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Advance the tick; called by [`Schedule::run`](crate::scheduler::Schedule::run)
+    pub fn increment_tick(&mut self) {
+        self.tick += 1;
+    }
+
     /// Spawn a new empty entity
     pub fn spawn_empty(&mut self) -> Entity {
         let entity_id = self.entities.spawn();
-        Entity::new(entity_id)
+        Entity::new(&self.env, entity_id)
     }
 
     /// Spawn a new entity with components
     pub fn spawn(&mut self, components: Vec<Component>) -> Entity {
         let entity_id = self.entities.spawn();
-        let mut entity = Entity::new(entity_id);
+        let entity = Entity::new(&self.env, entity_id);
 
         // Add components to the entity and storage
         for component in components {
@@ -53,52 +105,103 @@ impl World {
     }
 
     /// Add a component to an entity
-    pub fn add_component_to_entity(&mut self, entity_id: EntityId, component: Component) {
-        // Register the component type if not already registered
-        self.components
-            .register_component(component.component_type().clone());
+    ///
+    /// Returns `false` if the entity does not exist or the handle is stale.
+    pub fn add_component_to_entity(&mut self, entity_id: EntityId, component: Component) -> bool {
         // Add component type to entity
-        if let Some(mut entity) = self.entities.get_entity_mut(entity_id) {
-            entity.add_component_type(component.component_type().clone());
-            // Since we can't modify the entity in place, we need to update it
-            // This is a limitation of the Soroban SDK
-        }
+        let added = match self.entities.get_entity_mut(entity_id) {
+            Some(mut entity) => {
+                let added = !entity.has_component(component.component_type());
+                if added {
+                    entity.add_component_type(component.component_type().clone());
+                }
+                added
+            }
+            None => return false,
+        };
+        let component_type = component.component_type().clone();
+        // Register the component type if not already registered
+        self.components.register_component(component_type.clone());
         // Store the component data
         self.storage.add_component(entity_id, component);
+        self.change_ticks.mark_component(entity_id, &component_type);
+        if added {
+            self.run_hook(|hooks| hooks.on_add, entity_id, &component_type);
+        }
+        self.run_hook(|hooks| hooks.on_insert, entity_id, &component_type);
+        true
     }
 
     /// Remove a component from an entity
+    ///
+    /// The component's `on_remove` hook runs after the type is detached from
+    /// the entity but before its data leaves storage.
     pub fn remove_component_from_entity(
         &mut self,
         entity_id: EntityId,
         component_type: &Symbol,
     ) -> bool {
         // Remove component type from entity
-        if let Some(mut entity) = self.entities.get_entity_mut(entity_id) {
-            entity.remove_component_type(component_type);
-            // Since we can't modify the entity in place, we need to update it
-            // This is a limitation of the Soroban SDK
+        let detached = match self.entities.get_entity_mut(entity_id) {
+            Some(mut entity) => entity.remove_component_type(component_type),
+            None => false,
+        };
+        if detached {
+            self.run_hook(|hooks| hooks.on_remove, entity_id, component_type);
         }
         // Remove component data from storage
-        self.storage
-            .remove_component(entity_id, component_type.clone())
+        let removed = self
+            .storage
+            .remove_component(entity_id, component_type.clone());
+        self.change_ticks
+            .remove_component(entity_id, component_type);
+        removed || detached
     }
 
     /// Get a component from an entity
     pub fn get_component(&self, entity_id: EntityId, component_type: &Symbol) -> Option<Component> {
+        if !self.entities.exists(entity_id) {
+            return None;
+        }
         self.storage
             .get_component(entity_id, component_type.clone())
     }
 
-    /// Get a mutable reference to a component from an entity
+    /// Get a mutable handle to a component from an entity
+    ///
+    /// Changes made through the handle are written back when it is dropped,
+    /// through [`World::add_component_to_entity`], so the component's
+    /// `on_insert` hook runs.
     pub fn get_component_mut(
         &mut self,
         entity_id: EntityId,
         component_type: &Symbol,
-    ) -> Option<Component> {
-        // Since we simplified storage, we'll need to implement this differently
-        // For now, return a clone of the component if it exists
-        self.get_component(entity_id, component_type)
+    ) -> Option<ComponentMut<'_>> {
+        if !self.has_component(entity_id, component_type) {
+            return None;
+        }
+        let component = self.get_component(entity_id, component_type)?;
+        Some(ComponentMut {
+            world: self,
+            entity_id,
+            component,
+        })
+    }
+
+    /// Modify a component's raw data in place and write it back
+    ///
+    /// Returns `false` if the entity has no such component.
+    pub fn modify<F>(&mut self, entity_id: EntityId, component_type: &Symbol, f: F) -> bool
+    where
+        F: FnOnce(&mut Bytes),
+    {
+        match self.get_component_mut(entity_id, component_type) {
+            Some(mut component) => {
+                f(component.data_mut());
+                true
+            }
+            None => false,
+        }
     }
 
     /// Check if an entity has a specific component
@@ -110,17 +213,101 @@ impl World {
         }
     }
 
+    /// Insert a typed component on an entity, replacing any existing value
+    ///
+    /// Returns `false` if the entity does not exist or the handle is stale.
+    pub fn insert<T: ComponentTrait>(&mut self, entity_id: EntityId, value: T) -> bool {
+        let component = Component::with_storage(
+            T::component_type(),
+            value.serialize(&self.env),
+            T::default_storage(),
+        );
+        self.add_component_to_entity(entity_id, component)
+    }
+
+    /// Get a typed component from an entity
+    pub fn get<T: ComponentTrait>(&self, entity_id: EntityId) -> Option<T> {
+        let component = self.get_component(entity_id, &T::component_type())?;
+        T::deserialize(&self.env, component.data())
+    }
+
+    /// Get a typed component from an entity, or its default value if missing
+    pub fn get_or_default<T: ComponentTrait + Default>(&self, entity_id: EntityId) -> T {
+        self.get(entity_id).unwrap_or_default()
+    }
+
+    /// Check if an entity has a typed component
+    pub fn has<T: ComponentTrait>(&self, entity_id: EntityId) -> bool {
+        self.entities.exists(entity_id)
+            && self.storage.has_component(entity_id, T::component_type())
+    }
+
+    /// Modify a typed component in place and write it back
+    ///
+    /// Returns `false` if the entity has no such component.
+    pub fn update<T, F>(&mut self, entity_id: EntityId, f: F) -> bool
+    where
+        T: ComponentTrait,
+        F: FnOnce(&mut T),
+    {
+        match self.get::<T>(entity_id) {
+            Some(mut value) => {
+                f(&mut value);
+                self.insert(entity_id, value)
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a typed component from an entity, returning its last value
+    pub fn remove<T: ComponentTrait>(&mut self, entity_id: EntityId) -> Option<T> {
+        let value = self.get::<T>(entity_id)?;
+        self.remove_component_from_entity(entity_id, &T::component_type());
+        Some(value)
+    }
+
     /// Despawn an entity and remove all its components
+    ///
+    /// Relations to and from the entity are removed, and entities on the
+    /// other side of a cascading [`DespawnPolicy`](crate::relation::DespawnPolicy)
+    /// relation are despawned as well.
+    ///
+    /// Each component's `on_despawn` and then `on_remove` hook runs while its
+    /// data is still in storage.
     pub fn despawn(&mut self, entity_id: EntityId) -> bool {
-        if let Some(entity) = self.entities.get_entity(entity_id) {
-            // Remove all components from storage
-            let component_types = entity.component_types().clone();
-            for i in 0..component_types.len() {
-                let ctype = component_types.get(i).unwrap();
-                self.storage.remove_component(entity_id, ctype.clone());
+        let Some(entity) = self.entities.get_entity(entity_id) else {
+            return false;
+        };
+        if self.components.has_hooks() {
+            for component_type in entity.component_types().iter() {
+                // Detach first, so a hook despawning this entity again does
+                // not run the same hooks twice
+                let detached = match self.entities.get_entity_mut(entity_id) {
+                    Some(mut entity) => entity.remove_component_type(&component_type),
+                    None => false,
+                };
+                if detached {
+                    self.run_hook(|hooks| hooks.on_despawn, entity_id, &component_type);
+                    self.run_hook(|hooks| hooks.on_remove, entity_id, &component_type);
+                }
+            }
+            if !self.entities.exists(entity_id) {
+                return true;
             }
         }
-        self.entities.despawn(entity_id)
+        self.unlink_hierarchy(entity_id);
+        // Remove all components from storage
+        self.storage.remove_entity(entity_id);
+        for component_type in entity.component_types().iter() {
+            self.change_ticks
+                .remove_component(entity_id, &component_type);
+        }
+        self.entities.despawn(entity_id);
+        self.remove_entity_observers(entity_id);
+        for cascaded in self.unlink_despawned(entity_id) {
+            self.despawn(cascaded);
+        }
+        true
     }
 
     /// Get the total number of entities
@@ -143,93 +330,210 @@ impl World {
         self.entities.get_entity(entity_id)
     }
 
-    /// Get a mutable reference to an entity by ID
-    pub fn get_entity_mut(&mut self, entity_id: EntityId) -> Option<Entity> {
+    /// Get a mutable handle to an entity by ID, written back on drop
+    pub fn get_entity_mut(&mut self, entity_id: EntityId) -> Option<EntityMut<'_>> {
         self.entities.get_entity_mut(entity_id)
     }
 
-    /// Add a resource to the world
+    /// Add a resource, replacing any existing resource of the same type
     pub fn add_resource(&mut self, resource: Resource) {
-        self.resources.push_back(resource);
+        self.insert_resource_data(resource.resource_type, resource.data);
     }
 
     /// Get a resource by type
     pub fn get_resource(&self, resource_type: &Symbol) -> Option<Resource> {
-        for i in 0..self.resources.len() {
-            let res = self.resources.get(i).unwrap();
-            if res.resource_type() == resource_type {
-                return Some(res.clone());
-            }
-        }
-        None
+        let data = self.resources.get(resource_type.clone())?;
+        Some(Resource::new(resource_type.clone(), data))
     }
 
-    /// Get a mutable reference to a resource by type
-    pub fn get_resource_mut(&mut self, resource_type: &Symbol) -> Option<Resource> {
-        // Since soroban_sdk::Vec doesn't have iter_mut, we'll return a clone
-        self.get_resource(resource_type)
+    /// Get a mutable handle to a resource by type, written back on drop
+    pub fn get_resource_mut(&mut self, resource_type: &Symbol) -> Option<ResourceMut<'_>> {
+        let resource = self.get_resource(resource_type)?;
+        Some(ResourceMut {
+            resources: &mut self.resources,
+            change_ticks: &mut self.change_ticks,
+            resource_type: resource_type.clone(),
+            resource,
+        })
     }
 
-    /// Remove a resource from the world
-    pub fn remove_resource(&mut self, resource_type: &Symbol) -> Option<Resource> {
-        let mut found = None;
-        let mut new_resources = Vec::new(&soroban_sdk::Env::default());
-        for i in 0..self.resources.len() {
-            let res = self.resources.get(i).unwrap();
-            if res.resource_type() == resource_type {
-                found = Some(res.clone());
-            } else {
-                new_resources.push_back(res.clone());
-            }
-        }
-        if found.is_some() {
-            self.resources = new_resources;
+    /// Remove a resource by type
+    pub fn remove_resource_by_type(&mut self, resource_type: &Symbol) -> Option<Resource> {
+        let resource = self.get_resource(resource_type)?;
+        self.resources.remove(resource_type.clone());
+        self.change_ticks.resources.remove(resource_type.clone());
+        Some(resource)
+    }
+
+    /// Insert a typed resource, replacing any existing value
+    pub fn insert_resource<R: ResourceTrait>(&mut self, value: R) {
+        let data = value.serialize(&self.env);
+        self.insert_resource_data(R::resource_type(), data);
+    }
+
+    /// Store encoded resource data, stamping it as added or changed
+    fn insert_resource_data(&mut self, resource_type: Symbol, data: Bytes) {
+        self.change_ticks.mark_resource(&resource_type);
+        self.resources.set(resource_type, data);
+    }
+
+    /// Insert the default value of a typed resource unless one already exists
+    pub fn init_resource<R: ResourceTrait + Default>(&mut self) {
+        if !self.contains_resource::<R>() {
+            self.insert_resource(R::default());
         }
-        found
+    }
+
+    /// Check if the world has a resource of type `R`
+    pub fn contains_resource<R: ResourceTrait>(&self) -> bool {
+        self.resources.contains_key(R::resource_type())
+    }
+
+    /// Get a decoded copy of a typed resource
+    pub fn resource<R: ResourceTrait>(&self) -> Option<R> {
+        let data = self.resources.get(R::resource_type())?;
+        R::deserialize(&self.env, &data)
+    }
+
+    /// Get a mutable handle to a typed resource, written back on drop
+    pub fn resource_mut<R: ResourceTrait>(&mut self) -> Option<TypedResourceMut<'_, R>> {
+        let value = self.resource::<R>()?;
+        Some(TypedResourceMut { world: self, value })
+    }
+
+    /// Remove a typed resource, returning its value
+    pub fn remove_resource<R: ResourceTrait>(&mut self) -> Option<R> {
+        let value = self.resource::<R>();
+        self.resources.remove(R::resource_type());
+        self.change_ticks.resources.remove(R::resource_type());
+        value
     }
 
     /// Send an event
     pub fn send_event(&mut self, event: Event) {
-        self.events.push_back(event);
+        let mut queue = self.event_queue(event.event_type());
+        queue.send(event.clone());
+        self.events.set(event.event_type().clone(), queue);
+    }
+
+    /// Send a typed event
+    pub fn send<E: EventTrait>(&mut self, event: E) {
+        let data = event.serialize(&self.env);
+        self.send_event(Event::new(E::event_type(), data));
     }
 
-    /// Get all events of a specific type
+    /// Get all buffered events of a specific type, oldest first
     pub fn get_events(&self, event_type: &Symbol) -> Vec<Event> {
-        let env = soroban_sdk::Env::default();
-        let mut filtered = Vec::new(&env);
-        for i in 0..self.events.len() {
-            let event = self.events.get(i).unwrap();
-            if event.event_type() == event_type {
-                filtered.push_back(event.clone());
+        self.event_queue(event_type).events()
+    }
+
+    /// Get the typed event queue for `E`
+    pub fn events<E: EventTrait>(&self) -> Events<E> {
+        Events::from_queue(self.event_queue(&E::event_type()))
+    }
+
+    /// Read the events of type `E` not yet seen by `cursor`, advancing it
+    pub fn read_events<E: EventTrait>(&self, cursor: &mut EventCursor<E>) -> StdVec<E> {
+        self.events::<E>().read(cursor)
+    }
+
+    /// Read the events of type `E` not yet seen by the reader named `reader`
+    ///
+    /// The reader's cursor is kept in the world, so it is saved with it and
+    /// each event is read once across contract invocations.
+    pub fn read_events_as<E: EventTrait>(&mut self, reader: &Symbol) -> StdVec<E> {
+        let mut cursor = self.event_cursor::<E>(reader);
+        let events = self.read_events(&mut cursor);
+        self.set_event_cursor(reader, cursor);
+        events
+    }
+
+    /// Get the saved cursor of the reader named `reader` for events of type `E`
+    pub fn event_cursor<E: EventTrait>(&self, reader: &Symbol) -> EventCursor<E> {
+        self.event_queue(&E::event_type()).reader_cursor(reader)
+    }
+
+    /// Save the cursor of the reader named `reader` for events of type `E`
+    pub fn set_event_cursor<E: EventTrait>(&mut self, reader: &Symbol, cursor: EventCursor<E>) {
+        let mut queue = self.event_queue(&E::event_type());
+        queue.set_reader_cursor(reader, cursor);
+        self.events.set(E::event_type(), queue);
+    }
+
+    /// Rotate every event queue's buffers
+    ///
+    /// Events are dropped on the second update after they were sent.
+    /// [`Schedule::run`](crate::scheduler::Schedule::run) calls this after its
+    /// systems have run.
+    pub fn update_events(&mut self) {
+        for (event_type, mut queue) in self.events.iter() {
+            queue.update();
+            self.events.set(event_type, queue);
+        }
+    }
+
+    /// Publish events of type `E` as Soroban contract events on [`World::flush`]
+    pub fn mark_published<E: PublishEvent>(&mut self) {
+        let event_type = E::event_type();
+        if !self.publishers.iter().any(|(ty, _)| *ty == event_type) {
+            self.publishers.push((event_type, publish_as::<E>));
+        }
+    }
+
+    /// Check if events of type `E` are published on [`World::flush`]
+    pub fn is_published<E: PublishEvent>(&self) -> bool {
+        let event_type = E::event_type();
+        self.publishers.iter().any(|(ty, _)| *ty == event_type)
+    }
+
+    /// Publish every pending event of the marked types as a contract event
+    ///
+    /// Call this at the end of an invocation. Each event is published once,
+    /// even though it stays buffered for two updates; the publish position is
+    /// saved with the world. Returns the number of events published.
+    pub fn flush(&mut self) -> u32 {
+        let mut published = 0;
+        for (event_type, publish) in self.publishers.iter() {
+            let mut queue = self.event_queue(event_type);
+            let mut cursor = EventCursor::<()>::at(queue.published);
+            for event in queue.read(&mut cursor) {
+                if publish(&self.env, &event) {
+                    published += 1;
+                }
             }
+            queue.published = cursor.last_event_count();
+            self.events.set(event_type.clone(), queue);
         }
-        filtered
+        published
     }
 
     /// Clear all events
     pub fn clear_events(&mut self) {
-        let env = soroban_sdk::Env::default();
-        self.events = Vec::new(&env);
+        self.events = Map::new(&self.env);
+    }
+
+    fn event_queue(&self, event_type: &Symbol) -> EventQueue {
+        self.events
+            .get(event_type.clone())
+            .unwrap_or_else(|| EventQueue::new(&self.env))
     }
 
     /// Iterate over all entities
-    pub fn iter_entities(&self) -> EntityIterator {
+    pub fn iter_entities(&self) -> EntityIterator<'_> {
         self.entities.iter_entities()
     }
 
     /// Iterate over all entities mutably
-    pub fn iter_entities_mut(&mut self) -> EntityIteratorMut {
+    pub fn iter_entities_mut(&mut self) -> EntityIteratorMut<'_> {
         self.entities.iter_entities_mut()
     }
 
     /// Query entities with specific components
     pub fn query_entities(&self, component_types: &[Symbol]) -> Vec<EntityId> {
-        let env = soroban_sdk::Env::default();
-        let mut results = Vec::new(&env);
+        let mut results = Vec::new(&self.env);
         for entity in self.iter_entities() {
             let mut has_all_components = true;
-            for i in 0..component_types.len() {
-                let ctype = &component_types[i];
+            for ctype in component_types {
                 if !entity.has_component(ctype) {
                     has_all_components = false;
                     break;
@@ -242,16 +546,46 @@ impl World {
         results
     }
 
+    /// Decode the components in `Q` for every entity that has all of them
+    ///
+    /// ```ignore
+    /// for (entity, position, velocity) in world.query::<(Position, Velocity)>() {
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// Use [`Query::fetch`] to add `with`/`without` filters.
+    pub fn query<Q: QueryData>(&self) -> StdVec<Q::Item> {
+        Query::new(&self.env).fetch::<Q>(self)
+    }
+
+    /// Run `f` on the components in `Q` for every entity that has all of
+    /// them, writing modified components back
+    ///
+    /// Use [`Query::fetch_mut`] to add `with`/`without` filters.
+    pub fn query_mut<Q, F>(&mut self, f: F)
+    where
+        Q: QueryData,
+        F: FnMut(EntityId, &mut Q),
+    {
+        let query = Query::new(&self.env);
+        query.fetch_mut::<Q, F>(self, f)
+    }
+
     /// Clear all entities and components
     pub fn clear_entities(&mut self) {
-        self.entities = EntityManager::new();
-        self.storage = Storage::new();
+        self.entities = EntityManager::new(&self.env);
+        self.storage = Storage::new(&self.env);
+        self.relations = Map::new(&self.env);
+        self.change_ticks.components = Map::new(&self.env);
+        self.change_ticks.structure = Map::new(&self.env);
+        self.observers.retain(|observer| observer.is_global());
     }
 
     /// Clear all resources
     pub fn clear_resources(&mut self) {
-        let env = soroban_sdk::Env::default();
-        self.resources = Vec::new(&env);
+        self.resources = Map::new(&self.env);
+        self.change_ticks.resources = Map::new(&self.env);
     }
 
     /// Clear everything in the world
@@ -260,57 +594,279 @@ impl World {
         self.clear_resources();
         self.clear_events();
     }
+
+    /// Persist the whole world to the contract's persistent storage under
+    /// `key`
+    ///
+    /// Entities (including the ID allocator state), registered component types,
+    /// component data, resources, pending events, relations and change ticks
+    /// are all written. The world is a single persistent entry rather than
+    /// instance storage, which is loaded on every call and has a small size
+    /// limit.
+    ///
+    /// Like any persistent entry, the saved world has a time to live: the
+    /// contract must keep it alive with [`World::extend_ttl`], typically on
+    /// every invocation, or it is archived and must be restored before it can
+    /// be loaded again.
+    pub fn save<K>(&self, key: &K)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        self.env.storage().persistent().set(key, self);
+    }
+
+    /// Load a world previously written with [`World::save`]
+    ///
+    /// Returns `None` if nothing is stored under `key`.
+    pub fn load<K>(env: &Env, key: &K) -> Option<World>
+    where
+        K: IntoVal<Env, Val>,
+    {
+        env.storage().persistent().get(key)
+    }
+
+    /// Check if a world has been saved under `key`
+    pub fn is_saved<K>(env: &Env, key: &K) -> bool
+    where
+        K: IntoVal<Env, Val>,
+    {
+        env.storage().persistent().has(key)
+    }
+
+    /// Extend the time to live of the world saved under `key`
+    ///
+    /// If fewer than `threshold` ledgers remain, the entry is extended to live
+    /// for `extend_to` ledgers from now. Panics if no world is saved under
+    /// `key`.
+    pub fn extend_ttl<K>(env: &Env, key: &K, threshold: u32, extend_to: u32)
+    where
+        K: IntoVal<Env, Val>,
+    {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, threshold, extend_to);
+    }
 }
 
-impl Default for World {
-    fn default() -> Self {
-        Self::new()
+/// Mutable handle to a stored component
+///
+/// Holds a copy of the component and writes it back on drop, running the
+/// component's `on_insert` hook.
+pub struct ComponentMut<'w> {
+    world: &'w mut World,
+    entity_id: EntityId,
+    component: Component,
+}
+
+impl Deref for ComponentMut<'_> {
+    type Target = Component;
+
+    fn deref(&self) -> &Component {
+        &self.component
+    }
+}
+
+impl DerefMut for ComponentMut<'_> {
+    fn deref_mut(&mut self) -> &mut Component {
+        &mut self.component
+    }
+}
+
+impl Drop for ComponentMut<'_> {
+    fn drop(&mut self) {
+        self.world
+            .add_component_to_entity(self.entity_id, self.component.clone());
+    }
+}
+
+/// Mutable handle to a stored resource
+///
+/// Holds a copy of the resource and writes its data back on drop.
+pub struct ResourceMut<'w> {
+    resources: &'w mut Map<Symbol, Bytes>,
+    change_ticks: &'w mut ChangeTicks,
+    resource_type: Symbol,
+    resource: Resource,
+}
+
+impl Deref for ResourceMut<'_> {
+    type Target = Resource;
+
+    fn deref(&self) -> &Resource {
+        &self.resource
+    }
+}
+
+impl DerefMut for ResourceMut<'_> {
+    fn deref_mut(&mut self) -> &mut Resource {
+        &mut self.resource
+    }
+}
+
+impl Drop for ResourceMut<'_> {
+    fn drop(&mut self) {
+        self.resources
+            .set(self.resource_type.clone(), self.resource.data.clone());
+        self.change_ticks.mark_resource(&self.resource_type);
+    }
+}
+
+/// Mutable handle to a decoded typed resource
+///
+/// Holds the decoded value and encodes it back into the world on drop.
+pub struct TypedResourceMut<'w, R: ResourceTrait> {
+    world: &'w mut World,
+    value: R,
+}
+
+impl<R: ResourceTrait> Deref for TypedResourceMut<'_, R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.value
+    }
+}
+
+impl<R: ResourceTrait> DerefMut for TypedResourceMut<'_, R> {
+    fn deref_mut(&mut self) -> &mut R {
+        &mut self.value
+    }
+}
+
+impl<R: ResourceTrait> Drop for TypedResourceMut<'_, R> {
+    fn drop(&mut self) {
+        let data = self.value.serialize(&self.world.env);
+        self.world.insert_resource_data(R::resource_type(), data);
+    }
+}
+
+impl fmt::Debug for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("World")
+            .field("entities", &self.entities)
+            .field("components", &self.components)
+            .field("storage", &self.storage)
+            .field("resources", &self.resources)
+            .field("events", &self.events)
+            .field("relations", &self.relations)
+            .field("change_ticks", &self.change_ticks)
+            .field("tick", &self.tick)
+            .finish_non_exhaustive()
+    }
+}
+
+// Soroban SDK trait implementations for World
+impl IntoVal<Env, Val> for World {
+    fn into_val(&self, env: &Env) -> Val {
+        let entities: Val = self.entities.into_val(env);
+        let components: Val = self.components.into_val(env);
+        let storage: Val = self.storage.clone().into_val(env);
+        (
+            entities,
+            components,
+            storage,
+            self.resources.clone(),
+            self.events.clone(),
+            self.relations.clone(),
+            self.change_ticks.clone(),
+            self.tick,
+        )
+            .into_val(env)
+    }
+}
+
+/// Saved form of a world: entities, components and storage as raw values,
+/// then resources, events, relations, change ticks and the tick
+type SavedWorld = (
+    Val,
+    Val,
+    Val,
+    Map<Symbol, Bytes>,
+    Map<Symbol, EventQueue>,
+    Map<Symbol, Relation>,
+    ChangeTicks,
+    u64,
+);
+
+impl TryFromVal<Env, Val> for World {
+    type Error = soroban_sdk::ConversionError;
+
+    fn try_from_val(env: &Env, val: &Val) -> Result<Self, Self::Error> {
+        let (
+            entities_val,
+            components_val,
+            storage_val,
+            resources,
+            events,
+            relations,
+            change_ticks,
+            tick,
+        ): SavedWorld = TryFromVal::try_from_val(env, val)?;
+        Ok(World {
+            env: env.clone(),
+            entities: TryFromVal::try_from_val(env, &entities_val)?,
+            components: TryFromVal::try_from_val(env, &components_val)?,
+            storage: TryFromVal::try_from_val(env, &storage_val)?,
+            resources,
+            events,
+            relations,
+            change_ticks,
+            tick,
+            publishers: StdVec::new(),
+            observers: StdVec::new(),
+            next_observer_id: 0,
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{symbol_short, Env};
+    use crate::components::{Position, Velocity};
+    use soroban_sdk::{contract, symbol_short, Env};
+
+    #[contract]
+    pub struct TestContract;
 
     #[test]
     fn test_world_creation() {
-        let world = World::new();
+        let env = Env::default();
+        let world = World::new(&env);
         assert_eq!(world.entity_count(), 0);
         assert_eq!(world.component_count(), 0);
     }
 
     #[test]
     fn test_entity_spawn() {
-        let mut world = World::new();
+        let env = Env::default();
+        let mut world = World::new(&env);
         let entity = world.spawn_empty();
         assert_eq!(world.entity_count(), 1);
         assert!(world.exists(entity.id()));
     }
 
-    // TODO: This test requires sharing Env between World and test objects
-    // which is not straightforward with current architecture
-    // #[test]
-    // fn test_component_management() {
-    //     let mut world = World::new();
-    //     let entity_id = world.spawn_empty().id();
-    //     let env = Env::default();
-    //
-    //     let component_type = symbol_short!("test");
-    //     let mut component_data = soroban_sdk::Bytes::new(&env);
-    //     component_data.append(&soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]));
-    //     let component = Component::new(component_type, component_data);
-    //
-    //     world.add_component_to_entity(entity_id, component);
-    //     assert!(world.has_component(entity_id, &symbol_short!("test")));
-    //
-    //     let retrieved_component = world.get_component(entity_id, &symbol_short!("test"));
-    //     assert!(retrieved_component.is_some());
-    // }
+    #[test]
+    fn test_component_management() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity_id = world.spawn_empty().id();
+
+        let component_type = symbol_short!("test");
+        let mut component_data = soroban_sdk::Bytes::new(&env);
+        component_data.append(&soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]));
+        let component = Component::new(component_type, component_data);
+
+        world.add_component_to_entity(entity_id, component);
+        assert!(world.has_component(entity_id, &symbol_short!("test")));
+
+        let retrieved_component = world.get_component(entity_id, &symbol_short!("test"));
+        assert!(retrieved_component.is_some());
+    }
 
     #[test]
     fn test_entity_despawn() {
-        let mut world = World::new();
+        let env = Env::default();
+        let mut world = World::new(&env);
         let entity_id = world.spawn_empty().id();
         assert_eq!(world.entity_count(), 1);
 
@@ -319,42 +875,315 @@ mod tests {
         assert!(!world.exists(entity_id));
     }
 
-    // TODO: This test requires sharing Env between World and test objects
-    // which is not straightforward with current architecture
-    // #[test]
-    // fn test_resource_management() {
-    //     let mut world = World::new();
-    //     let env = Env::default();
-    //     let resource_type = symbol_short!("testres");
-    //     let mut resource_data = soroban_sdk::Bytes::new(&env);
-    //     resource_data.append(&soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]));
-    //     let resource = Resource::new(resource_type, resource_data);
-    //
-    //     world.add_resource(resource);
-    //     assert!(world.get_resource(&symbol_short!("testres")).is_some());
-    //
-    //     let removed_resource = world.remove_resource(&symbol_short!("testres"));
-    //     assert!(removed_resource.is_some());
-    //     assert!(world.get_resource(&symbol_short!("testres")).is_none());
-    // }
-
-    // TODO: This test requires sharing Env between World and test objects
-    // which is not straightforward with current architecture
-    // #[test]
-    // fn test_event_system() {
-    //     let mut world = World::new();
-    //     let env = Env::default();
-    //     let event_type = symbol_short!("testevent");
-    //     let mut event_data = soroban_sdk::Bytes::new(&env);
-    //     event_data.append(&soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]));
-    //     let event = Event::new(event_type, event_data);
-    //
-    //     world.send_event(event);
-    //     let events = world.get_events(&symbol_short!("testevent"));
-    //     assert_eq!(events.len(), 1);
-    //
-    //     world.clear_events();
-    //     let events = world.get_events(&symbol_short!("testevent"));
-    //     assert_eq!(events.len(), 0);
-    // }
+    #[test]
+    fn test_stale_entity_handle_is_rejected() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let stale = world.spawn_empty().id();
+        world.insert(stale, Position::new(1, 1));
+        assert!(world.despawn(stale));
+
+        let reused = world.spawn_empty().id();
+        assert_eq!(reused.id(), stale.id());
+        assert!(world.insert(reused, Position::new(9, 9)));
+
+        assert!(!world.exists(stale));
+        assert!(!world.has::<Position>(stale));
+        assert_eq!(world.get::<Position>(stale), None);
+        assert!(!world.insert(stale, Velocity::new(1, 0)));
+        assert!(!world.has::<Velocity>(reused));
+        assert_eq!(world.get::<Position>(reused), Some(Position::new(9, 9)));
+    }
+
+    #[test]
+    fn test_resource_management() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let resource_type = symbol_short!("testres");
+        let mut resource_data = soroban_sdk::Bytes::new(&env);
+        resource_data.append(&soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]));
+        let resource = Resource::new(resource_type, resource_data);
+
+        world.add_resource(resource);
+        assert!(world.get_resource(&symbol_short!("testres")).is_some());
+
+        let removed_resource = world.remove_resource_by_type(&symbol_short!("testres"));
+        assert!(removed_resource.is_some());
+        assert!(world.get_resource(&symbol_short!("testres")).is_none());
+    }
+
+    #[test]
+    fn test_event_system() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let event_type = symbol_short!("testevent");
+        let mut event_data = soroban_sdk::Bytes::new(&env);
+        event_data.append(&soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]));
+        let event = Event::new(event_type, event_data);
+
+        world.send_event(event);
+        let events = world.get_events(&symbol_short!("testevent"));
+        assert_eq!(events.len(), 1);
+
+        world.clear_events();
+        let events = world.get_events(&symbol_short!("testevent"));
+        assert_eq!(events.len(), 0);
+    }
+
+    #[test]
+    fn test_load_missing_world() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            assert!(!World::is_saved(&env, &symbol_short!("world")));
+            assert!(World::load(&env, &symbol_short!("world")).is_none());
+        });
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let mut world = World::new(&env);
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.despawn(a);
+
+        let data = soroban_sdk::Bytes::from_array(&env, &[1, 2, 3, 4]);
+        world.add_component_to_entity(b, Component::new(symbol_short!("test"), data.clone()));
+        world.add_resource(Resource::new(symbol_short!("testres"), data.clone()));
+        world.send_event(Event::new(symbol_short!("testevent"), data.clone()));
+        world.increment_tick();
+
+        env.as_contract(&contract_id, || {
+            world.save(&symbol_short!("world"));
+            assert!(World::is_saved(&env, &symbol_short!("world")));
+            World::extend_ttl(&env, &symbol_short!("world"), 5000, 10_000);
+            let ttl = env.storage().persistent().get_ttl(&symbol_short!("world"));
+            assert_eq!(ttl, 10_000);
+
+            let mut loaded = World::load(&env, &symbol_short!("world")).unwrap();
+            assert_eq!(loaded.entity_count(), 1);
+            assert!(loaded.exists(b));
+            assert!(!loaded.exists(a));
+            assert_eq!(loaded.component_count(), 1);
+            assert_eq!(
+                loaded
+                    .get_component(b, &symbol_short!("test"))
+                    .unwrap()
+                    .data(),
+                &data
+            );
+            assert!(loaded.get_resource(&symbol_short!("testres")).is_some());
+            assert_eq!(loaded.get_events(&symbol_short!("testevent")).len(), 1);
+            assert_eq!(loaded.tick(), 1);
+
+            // The free list survives the round trip, so the despawned ID is reused
+            assert_eq!(loaded.spawn_empty().id().id(), a.id());
+        });
+    }
+
+    #[test]
+    fn test_typed_component_api() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity = world.spawn_empty().id();
+
+        world.insert(entity, Position::new(10, -20));
+        world.insert(entity, Velocity::new(1, 2));
+        assert!(world.has::<Position>(entity));
+        assert_eq!(world.get::<Position>(entity), Some(Position::new(10, -20)));
+        assert_eq!(world.get::<Velocity>(entity), Some(Velocity::new(1, 2)));
+
+        assert!(world.update::<Position, _>(entity, |pos| {
+            pos.x += 5;
+            pos.y += 5;
+        }));
+        assert_eq!(world.get::<Position>(entity), Some(Position::new(15, -15)));
+
+        assert_eq!(world.remove::<Velocity>(entity), Some(Velocity::new(1, 2)));
+        assert_eq!(world.get::<Velocity>(entity), None);
+        assert_eq!(world.remove::<Velocity>(entity), None);
+    }
+
+    #[test]
+    fn test_typed_component_missing() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity = world.spawn_empty().id();
+
+        assert!(!world.has::<Position>(entity));
+        assert_eq!(world.get::<Position>(entity), None);
+        assert_eq!(
+            world.get_or_default::<Velocity>(entity),
+            Velocity::new(0, 0)
+        );
+        assert!(!world.update::<Velocity, _>(entity, |vel| vel.x = 1));
+        assert!(!world.has::<Velocity>(entity));
+    }
+
+    #[test]
+    fn test_query_finds_entities_after_add_component() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let moving = world.spawn_empty().id();
+        let still = world.spawn_empty().id();
+
+        world.insert(moving, Position::new(0, 0));
+        world.insert(moving, Velocity::new(1, 1));
+        world.insert(still, Position::new(5, 5));
+
+        let results = world.query_entities(&[Position::component_type()]);
+        assert_eq!(results.len(), 2);
+        let results =
+            world.query_entities(&[Position::component_type(), Velocity::component_type()]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results.get(0), Some(moving));
+
+        // Re-inserting does not record the component type twice
+        world.insert(moving, Position::new(1, 1));
+        assert_eq!(world.get_entity(moving).unwrap().component_count(), 2);
+
+        world.remove::<Velocity>(moving);
+        assert!(!world.has_component(moving, &Velocity::component_type()));
+        let results =
+            world.query_entities(&[Position::component_type(), Velocity::component_type()]);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_component_mut_writes_back() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        let entity = world.spawn_empty().id();
+        world.insert(entity, Position::new(1, 2));
+
+        {
+            let mut component = world
+                .get_component_mut(entity, &Position::component_type())
+                .unwrap();
+            *component.data_mut() = Position::new(3, 4).serialize(&env);
+        }
+        assert_eq!(world.get::<Position>(entity), Some(Position::new(3, 4)));
+
+        assert!(world.modify(entity, &Position::component_type(), |data| {
+            *data = Position::new(5, 6).serialize(&env);
+        }));
+        assert_eq!(world.get::<Position>(entity), Some(Position::new(5, 6)));
+        assert!(!world.modify(entity, &Velocity::component_type(), |_| {}));
+    }
+
+    #[test]
+    fn test_resource_mut_writes_back() {
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world.add_resource(Resource::new(
+            symbol_short!("testres"),
+            soroban_sdk::Bytes::from_array(&env, &[1]),
+        ));
+
+        world
+            .get_resource_mut(&symbol_short!("testres"))
+            .unwrap()
+            .data_mut()
+            .push_back(2);
+
+        assert_eq!(
+            world
+                .get_resource(&symbol_short!("testres"))
+                .unwrap()
+                .data(),
+            &soroban_sdk::Bytes::from_array(&env, &[1, 2])
+        );
+    }
+
+    #[test]
+    fn test_typed_resources_replace() {
+        use crate::resource::GameState;
+
+        let env = Env::default();
+        let mut world = World::new(&env);
+        world.init_resource::<GameState>();
+        assert_eq!(world.resource::<GameState>(), Some(GameState::new()));
+
+        let mut state = GameState::new();
+        state.increment_score(7);
+        world.insert_resource(state.clone());
+        world.init_resource::<GameState>();
+        assert_eq!(world.resources.len(), 1);
+        assert_eq!(world.resource::<GameState>(), Some(state));
+
+        world.resource_mut::<GameState>().unwrap().next_level();
+        assert_eq!(world.resource::<GameState>().unwrap().level, 2);
+
+        let removed = world.remove_resource::<GameState>().unwrap();
+        assert_eq!(removed.score, 7);
+        assert!(!world.contains_resource::<GameState>());
+        assert!(world.resource_mut::<GameState>().is_none());
+    }
+
+    #[test]
+    fn test_flush_publishes_marked_events_once() {
+        use crate::event::{CollisionEvent, DamageEvent};
+        use crate::testutils::{assert_published, published_count};
+
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        env.as_contract(&contract_id, || {
+            let mut world = World::new(&env);
+            world.mark_published::<DamageEvent>();
+            assert!(world.is_published::<DamageEvent>());
+            assert!(!world.is_published::<CollisionEvent>());
+
+            let damage = DamageEvent::new(EntityId::new(4, 1).to_bits(), 3, symbol_short!("fire"));
+            world.send(damage.clone());
+            world.send(CollisionEvent::new(1, 2, symbol_short!("physical")));
+            assert_eq!(world.flush(), 1);
+
+            // Still buffered after an update, but already published
+            world.update_events();
+            assert_eq!(world.flush(), 0);
+            assert_eq!(published_count::<DamageEvent>(&env, &contract_id), 1);
+            assert_eq!(published_count::<CollisionEvent>(&env, &contract_id), 0);
+            assert_published(&env, &contract_id, &damage);
+        });
+    }
+
+    /// CPU instructions spent reading one component through the world, with
+    /// `entities` entities holding two components each
+    fn world_lookup_cost(entities: u32) -> u64 {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let mut world = World::new(&env);
+        let mut ids = StdVec::new();
+        for i in 0..entities as i32 {
+            let entity = world.spawn_empty().id();
+            world.insert(entity, Position::new(i, i));
+            world.insert(entity, Velocity::new(1, 1));
+            ids.push(entity);
+        }
+
+        let target = ids[ids.len() / 2];
+        let budget = env.cost_estimate().budget();
+        let before = budget.cpu_instruction_cost();
+        assert!(world.get::<Velocity>(target).is_some());
+        assert!(world
+            .get_component(target, &symbol_short!("position"))
+            .is_some());
+        budget.cpu_instruction_cost() - before
+    }
+
+    #[test]
+    fn bench_world_lookup_cost_scaling() {
+        let cost_10 = world_lookup_cost(10);
+        let cost_100 = world_lookup_cost(100);
+        let cost_1000 = world_lookup_cost(1000);
+
+        // Same budget as the storage benchmark: handle validation and the
+        // component lookup must both be keyed, not scans
+        assert!(cost_100 < cost_10 * 4, "10: {cost_10}, 100: {cost_100}");
+        assert!(cost_1000 < cost_10 * 8, "10: {cost_10}, 1000: {cost_1000}");
+    }
 }